@@ -0,0 +1,691 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ButtonStyle, ChannelId, ChannelType, ComponentInteraction, CreateActionRow, CreateButton,
+    CreateChannel, CreateEmbed, CreateInteractionResponse, CreateInteractionResponseFollowup,
+    CreateInteractionResponseMessage, CreateMessage, EditMessage, GuildChannel,
+    PermissionOverwrite, PermissionOverwriteType, Permissions, RoleId, User,
+};
+use uuid::Uuid;
+
+use crate::{
+    commands::{
+        run::is_interaction_from_admin,
+        whitelist::{apply_access_policy, build_whitelist_report_embed},
+    },
+    database::model::{
+        application::{
+            Application, ApplicationContent, ApplicationModelController, Vote, VoteTally,
+        },
+        member::{CreateMember, MemberModelController},
+    },
+    error::respond_error,
+    util::{
+        builder::log_embed,
+        format::{display, escape_markdown, fdisplay, user_mention},
+        mojang::MojangAPI,
+        random_utils::respond_ephemeral,
+    },
+    Context as AppContext, Data,
+};
+
+pub const APPLICATION_VOTE_PREFIX: &str = "application_vote";
+
+type ApplicationContext<'a> = poise::ApplicationContext<'a, Data, anyhow::Error>;
+
+#[derive(Debug, poise::Modal)]
+#[name = "Member Application"]
+struct ApplicationModal {
+    #[name = "Minecraft IGN(s), separated by commas"]
+    #[placeholder = "Steve, Alex"]
+    #[max_length = 200]
+    igns: String,
+    #[name = "How old are you?"]
+    #[max_length = 3]
+    age: String,
+    #[name = "What is your timezone?"]
+    #[placeholder = "UTC+1"]
+    #[max_length = 50]
+    timezone: String,
+    #[name = "What is your technical minecraft experience?"]
+    #[paragraph]
+    #[max_length = 1000]
+    experience: String,
+    #[name = "Why do you want to join?"]
+    #[paragraph]
+    #[max_length = 1000]
+    about: String,
+}
+
+/// Apply for membership & handle applications.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("apply", "accept", "deny"),
+    subcommand_required
+)]
+pub async fn application(_: AppContext<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Apply to become a member.
+#[poise::command(slash_command, guild_only = true)]
+async fn apply(ctx: ApplicationContext<'_>) -> anyhow::Result<()> {
+    let db_pool = &ctx.data().db_pool;
+    let config = &ctx.data().config;
+
+    match ApplicationModelController::get_open_by_user(db_pool, &ctx.author().id).await {
+        Ok(None) => {}
+        Ok(Some(_)) => {
+            ctx.send(
+                CreateReply::default()
+                    .content("You already have an open application!")
+                    .ephemeral(true),
+            )
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            return respond_error(
+                "Failed to get your applications from the database",
+                e,
+                &ctx.into(),
+            )
+            .await;
+        }
+    }
+
+    if MemberModelController::get_by_id(db_pool, &ctx.author().id)
+        .await?
+        .is_some_and(|member| member.departed_at.is_none())
+    {
+        ctx.send(
+            CreateReply::default()
+                .content("You are already a member!")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let Some(modal) =
+        poise::execute_modal::<_, _, ApplicationModal>(ctx, None, Some(Duration::from_secs(900)))
+            .await?
+    else {
+        return Ok(());
+    };
+
+    let igns = modal
+        .igns
+        .split(',')
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .collect::<Vec<String>>();
+
+    if igns.is_empty() {
+        ctx.send(
+            CreateReply::default()
+                .content("Ign's cannot be empty!")
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let profiles = match MojangAPI::get_profiles(igns.clone()).await {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            return respond_error("Failed to get profiles from the Mojang API", e, &ctx.into())
+                .await;
+        }
+    };
+
+    if profiles.len() != igns.len() {
+        let missing = igns
+            .iter()
+            .filter(|ign| !profiles.iter().any(|p| p.name.eq_ignore_ascii_case(ign)))
+            .map(|ign| escape_markdown(ign.as_str()))
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        ctx.send(
+            CreateReply::default()
+                .content(format!("Could not find the minecraft accounts {missing}!"))
+                .ephemeral(true),
+        )
+        .await?;
+        return Ok(());
+    }
+
+    let content = ApplicationContent {
+        igns: profiles.into_iter().map(|p| p.name).collect(),
+        age: modal.age,
+        timezone: modal.timezone,
+        experience: modal.experience,
+        about: modal.about,
+    };
+
+    let application =
+        match ApplicationModelController::create(db_pool, &ctx.author().id, content).await {
+            Ok(application) => application,
+            Err(e) => {
+                return respond_error(
+                    "Failed to save your application in the database",
+                    e,
+                    &ctx.into(),
+                )
+                .await;
+            }
+        };
+
+    let channel_id = match open_application(ctx, &application).await {
+        Ok(channel_id) => channel_id,
+        Err(e) => {
+            // without a ticket channel nobody can handle the application, and an open one would
+            // keep the user from applying again
+            if let Err(delete_error) =
+                ApplicationModelController::delete(db_pool, application.id).await
+            {
+                tracing::error!(
+                    "Failed to delete application {} after it could not be opened: {delete_error}",
+                    application.id
+                );
+            }
+
+            return respond_error("Failed to open your application", e, &ctx.into()).await;
+        }
+    };
+
+    ctx.send(
+        CreateReply::default()
+            .content(format!(
+                "Your application was submitted! Follow up in <#{channel_id}>."
+            ))
+            .ephemeral(true),
+    )
+    .await?;
+
+    Ok(())
+}
+
+/// Creates the ticket channel of the application and posts it. The channel is deleted again if
+/// posting fails.
+async fn open_application(
+    ctx: ApplicationContext<'_>,
+    application: &Application,
+) -> anyhow::Result<ChannelId> {
+    let config = &ctx.data().config;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        anyhow::bail!("Failed to get guild id.");
+    };
+
+    let bot_id = ctx.serenity_context().cache.current_user().id;
+    let view = Permissions::VIEW_CHANNEL | Permissions::SEND_MESSAGES;
+
+    let overwrites = vec![
+        PermissionOverwrite {
+            allow: Permissions::empty(),
+            deny: Permissions::VIEW_CHANNEL,
+            kind: PermissionOverwriteType::Role(RoleId::new(guild_id.get())),
+        },
+        PermissionOverwrite {
+            allow: view,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(ctx.author().id),
+        },
+        PermissionOverwrite {
+            allow: view,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Member(bot_id),
+        },
+        PermissionOverwrite {
+            allow: view,
+            deny: Permissions::empty(),
+            kind: PermissionOverwriteType::Role(config.roles.admin),
+        },
+    ];
+
+    let channel = guild_id
+        .create_channel(
+            &ctx,
+            CreateChannel::new(format!("application-{}", ctx.author().name))
+                .kind(ChannelType::Text)
+                .category(config.categories.application)
+                .permissions(overwrites),
+        )
+        .await?;
+
+    if let Err(e) = post_application(ctx, application, &channel).await {
+        if let Err(delete_error) = channel.delete(&ctx).await {
+            tracing::error!(
+                "Failed to delete the channel of application {}: {delete_error}",
+                application.id
+            );
+        }
+
+        return Err(e);
+    }
+
+    Ok(channel.id)
+}
+
+/// Posts the application into its ticket channel and for voting, and links both to the row.
+async fn post_application(
+    ctx: ApplicationContext<'_>,
+    application: &Application,
+    channel: &GuildChannel,
+) -> anyhow::Result<()> {
+    let config = &ctx.data().config;
+
+    let embed = build_application_embed(application, ctx.author(), None, None);
+
+    channel
+        .send_message(
+            &ctx,
+            CreateMessage::new()
+                .content(format!(
+                    "Thank you for applying {}! The members will vote on your application and an admin will get back to you here.",
+                    user_mention(ctx.author())
+                ))
+                .embed(embed),
+        )
+        .await?;
+
+    let tally = VoteTally::default();
+    let embed = build_application_embed(application, ctx.author(), Some(&tally), None);
+
+    let voting_message = config
+        .channels
+        .application_voting
+        .send_message(
+            &ctx,
+            CreateMessage::new()
+                .embed(embed)
+                .components(vote_component(application.id)),
+        )
+        .await?;
+
+    if let Err(e) = ApplicationModelController::set_channels(
+        &ctx.data().db_pool,
+        application.id,
+        Some(channel.id),
+        Some(voting_message.id),
+    )
+    .await
+    {
+        if let Err(delete_error) = voting_message.delete(&ctx).await {
+            tracing::error!(
+                "Failed to delete the voting message of application {}: {delete_error}",
+                application.id
+            );
+        }
+
+        return Err(e);
+    }
+
+    Ok(())
+}
+
+/// Accept an application and add the applicant as a trial member.
+#[poise::command(slash_command, guild_only = true)]
+async fn accept(
+    ctx: AppContext<'_>,
+    #[description = "The applicant to accept."] user: User,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can accept applications!").await?;
+        return Ok(());
+    }
+
+    let db_pool = &ctx.data().db_pool;
+    let config = &ctx.data().config;
+
+    let Some(application) = ApplicationModelController::get_open_by_user(db_pool, &user.id).await?
+    else {
+        ctx.say(format!(
+            "{} does not have an open application.",
+            fdisplay(&user)
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let profiles = match MojangAPI::get_profiles(application.content.igns.clone()).await {
+        Ok(profiles) => profiles,
+        Err(e) => {
+            return respond_error("Failed to get profiles from the Mojang API", e, &ctx).await;
+        }
+    };
+
+    let names = profiles
+        .iter()
+        .map(|p| p.name.clone())
+        .collect::<Vec<String>>();
+
+    let create_member = CreateMember {
+        discord_id: user.id,
+        trial_member: true,
+        minecraft_uuids: profiles.into_iter().map(|p| p.id).collect::<Vec<Uuid>>(),
+        member_since: Utc::now().naive_utc(),
+    };
+
+    if let Err(e) = MemberModelController::create(db_pool, create_member).await {
+        return respond_error(
+            format!("Failed to add {} to the Memberlist", display(&user)),
+            e,
+            &ctx,
+        )
+        .await;
+    }
+
+    ApplicationModelController::close(db_pool, application.id).await?;
+
+    let Some(guild_id) = ctx.guild_id() else {
+        anyhow::bail!("Failed to get guild id.");
+    };
+
+    let mut content = format!("Accepted the application of {}.", fdisplay(&user));
+
+    let role_result = match guild_id.member(&ctx, user.id).await {
+        Ok(member) => member.add_role(&ctx, config.roles.trial).await,
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = role_result {
+        tracing::error!("Failed to add the trial role to {}: {e}", display(&user));
+        content.push_str(" Failed to add the trial role!");
+    }
+
+    let mut embeds = Vec::new();
+
+    for name in names {
+        match apply_access_policy(name.as_str(), true, &ctx.data().rcon, &config.minecraft).await {
+            Ok(results) => embeds.push(build_whitelist_report_embed(
+                ctx.author(),
+                format!("Whitelist Results for {name}"),
+                &results,
+            )),
+            Err(e) => {
+                tracing::error!("Failed to whitelist {name}: {e}");
+                content.push_str(&format!(" Failed to whitelist {name}!"));
+            }
+        }
+    }
+
+    let result = format!("Accepted by {}", fdisplay(ctx.author()));
+    close_application(&ctx, &application, &user, result).await;
+
+    if let Some(channel_id) = application.channel_id {
+        let message = format!(
+            "Congratulations {}, your application was accepted! Welcome to the server as a trial member.",
+            user_mention(&user)
+        );
+
+        if let Err(e) = channel_id.say(&ctx, message).await {
+            tracing::error!(
+                "Failed to notify {} of the accepted application: {e}",
+                display(&user)
+            );
+        }
+    }
+
+    let reply = embeds
+        .into_iter()
+        .take(10)
+        .fold(CreateReply::default().content(content), |reply, embed| {
+            reply.embed(embed)
+        });
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
+/// Deny an application.
+#[poise::command(slash_command, guild_only = true)]
+async fn deny(
+    ctx: AppContext<'_>,
+    #[description = "The applicant to deny."] user: User,
+    #[description = "The reason shown to the applicant."] reason: Option<String>,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can deny applications!").await?;
+        return Ok(());
+    }
+
+    let db_pool = &ctx.data().db_pool;
+
+    let Some(application) = ApplicationModelController::get_open_by_user(db_pool, &user.id).await?
+    else {
+        ctx.say(format!(
+            "{} does not have an open application.",
+            fdisplay(&user)
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    ApplicationModelController::close(db_pool, application.id).await?;
+
+    let result = format!("Denied by {}", fdisplay(ctx.author()));
+    close_application(&ctx, &application, &user, result).await;
+
+    if let Some(channel_id) = application.channel_id {
+        let mut message = format!(
+            "Sorry {}, your application was denied.",
+            user_mention(&user)
+        );
+
+        if let Some(reason) = reason {
+            message.push_str(&format!("\n**Reason**: {}", escape_markdown(reason)));
+        }
+
+        if let Err(e) = channel_id.say(&ctx, message).await {
+            tracing::error!(
+                "Failed to notify {} of the denied application: {e}",
+                display(&user)
+            );
+        }
+    }
+
+    ctx.say(format!("Denied the application of {}.", fdisplay(&user)))
+        .await?;
+
+    Ok(())
+}
+
+/// Handles the vote buttons below the voting message. Custom ids look like
+/// `application_vote:<application id>:<vote>`.
+pub async fn handle_application_vote(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let mut parts = component.data.custom_id.split(':').skip(1);
+
+    let (Some(Ok(id)), Some(Ok(vote))) = (
+        parts.next().map(str::parse::<i32>),
+        parts.next().map(str::parse::<Vote>),
+    ) else {
+        anyhow::bail!("Invalid vote button {}", component.data.custom_id);
+    };
+
+    let roles = &data.config.roles;
+    let can_vote = component.member.as_ref().is_some_and(|member| {
+        member.roles.contains(&roles.member) || member.roles.contains(&roles.admin)
+    });
+
+    if !can_vote {
+        return respond_ephemeral(component, ctx, "Only members can vote on applications!").await;
+    }
+
+    let Some(application) = ApplicationModelController::get_by_id(&data.db_pool, id).await? else {
+        return respond_ephemeral(component, ctx, "This application does not exist anymore!").await;
+    };
+
+    if !application.is_open {
+        return respond_ephemeral(component, ctx, "This application is already closed!").await;
+    }
+
+    ApplicationModelController::vote(&data.db_pool, id, &component.user.id, vote).await?;
+
+    let tally = ApplicationModelController::tally(&data.db_pool, id).await?;
+    let applicant = application.discord_id.to_user(ctx).await?;
+    let embed = build_application_embed(&application, &applicant, Some(&tally), None);
+
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new().embed(embed),
+            ),
+        )
+        .await?;
+
+    component
+        .create_followup(
+            ctx,
+            CreateInteractionResponseFollowup::new()
+                .content(format!("Your vote ({vote}) was recorded."))
+                .ephemeral(true),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Replaces the buttons of the voting message with the final tally and result.
+async fn close_application(
+    ctx: &AppContext<'_>,
+    application: &Application,
+    applicant: &User,
+    result: String,
+) {
+    let Some(message_id) = application.voting_message_id else {
+        return;
+    };
+
+    let tally = match ApplicationModelController::tally(&ctx.data().db_pool, application.id).await {
+        Ok(tally) => tally,
+        Err(e) => {
+            tracing::error!(
+                "Failed to tally the votes of application {}: {e}",
+                application.id
+            );
+            VoteTally::default()
+        }
+    };
+
+    let embed = build_application_embed(application, applicant, Some(&tally), Some(result));
+
+    if let Err(e) = ctx
+        .data()
+        .config
+        .channels
+        .application_voting
+        .edit_message(
+            ctx,
+            message_id,
+            EditMessage::new().embed(embed).components(vec![]),
+        )
+        .await
+    {
+        tracing::error!(
+            "Failed to close the voting message of application {}: {e}",
+            application.id
+        );
+    }
+}
+
+fn vote_component(id: i32) -> Vec<CreateActionRow> {
+    let yes = CreateButton::new(format!("{APPLICATION_VOTE_PREFIX}:{id}:yes"))
+        .label("Yes")
+        .style(ButtonStyle::Success);
+    let no = CreateButton::new(format!("{APPLICATION_VOTE_PREFIX}:{id}:no"))
+        .label("No")
+        .style(ButtonStyle::Danger);
+    let abstain = CreateButton::new(format!("{APPLICATION_VOTE_PREFIX}:{id}:abstain"))
+        .label("Abstain")
+        .style(ButtonStyle::Secondary);
+
+    vec![CreateActionRow::Buttons(vec![yes, no, abstain])]
+}
+
+fn build_application_embed(
+    application: &Application,
+    applicant: &User,
+    tally: Option<&VoteTally>,
+    result: Option<String>,
+) -> CreateEmbed {
+    let content = &application.content;
+
+    let igns = content
+        .igns
+        .iter()
+        .map(escape_markdown)
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let embed = log_embed()
+        .title(format!(
+            "Application #{} from {}",
+            application.id,
+            applicant.global_name.as_deref().unwrap_or(&applicant.name)
+        ))
+        .thumbnail(
+            applicant
+                .avatar_url()
+                .unwrap_or(applicant.default_avatar_url()),
+        )
+        .field("Applicant", fdisplay(applicant), false)
+        .field("Minecraft Usernames", igns, false)
+        .field("Age", field_value(&content.age), true)
+        .field("Timezone", field_value(&content.timezone), true)
+        .field(
+            "Technical Minecraft Experience",
+            field_value(&content.experience),
+            false,
+        )
+        .field(
+            "Why do you want to join?",
+            field_value(&content.about),
+            false,
+        );
+
+    let embed = match tally {
+        Some(tally) => embed.field(
+            "Votes",
+            format!(
+                "Yes: {} | No: {} | Abstain: {}",
+                tally.yes, tally.no, tally.abstain
+            ),
+            false,
+        ),
+        None => embed,
+    };
+
+    match result {
+        Some(result) => embed.field("Result", result, false),
+        None => embed,
+    }
+}
+
+/// Escapes user input and keeps it within the 1024 character limit of embed fields.
+fn field_value(input: &str) -> String {
+    let value = escape_markdown(input);
+
+    if value.chars().count() <= 1024 {
+        return value;
+    }
+
+    let mut value = value.chars().take(1021).collect::<String>();
+    value.push_str("...");
+    value
+}
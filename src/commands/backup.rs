@@ -16,11 +16,12 @@ use crate::config::PterodactylConfig;
 use crate::config::ServerConfig;
 use crate::util::random_utils::confirm_cancel_component;
 use crate::{
-    config::ServerChoice,
     error::respond_error,
     util::{
+        autocomplete::{autocomplete_backup, autocomplete_server},
         builder::default_embed,
         format::{display_bytes, inline_code, time, TimestampStyle},
+        paginate::send_paginated,
         pterodactyl::PteroClient,
         random_utils::maybe_set_guild_thumbnail,
     },
@@ -31,7 +32,7 @@ use crate::{
 #[poise::command(
     slash_command,
     guild_only = true,
-    subcommands("list", "details", "create", "delete"),
+    subcommands("list", "details", "create", "delete", "lock", "unlock"),
     subcommand_required,
     track_edits
 )]
@@ -43,7 +44,9 @@ pub async fn backup(_: AppContext<'_>) -> anyhow::Result<()> {
 #[poise::command(slash_command, guild_only = true)]
 async fn list(
     ctx: AppContext<'_>,
-    #[description = "Choose a server."] server_choice: ServerChoice,
+    #[description = "Choose a server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
 ) -> anyhow::Result<()> {
     ctx.defer().await?;
 
@@ -52,25 +55,27 @@ async fn list(
         .await
         .context("Failed to fetch the guild this interaction was created in")?;
 
-    let backups = match PteroClient::backup_list(
-        &ctx.data().config.pterodactyl,
-        ctx.data().config.minecraft.get(server_choice),
-    )
-    .await
-    {
-        Ok(b) => b,
-        Err(e) => {
-            return respond_error(
-                format!("Failed to get backup list from {server_choice}"),
-                e,
-                &ctx,
-            )
-            .await;
-        }
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
     };
 
+    let backups =
+        match PteroClient::backup_list(&ctx.data().config.pterodactyl, server_config).await {
+            Ok(b) => b,
+            Err(e) => {
+                return respond_error(
+                    format!("Failed to get backup list from {server_config}"),
+                    e,
+                    &ctx,
+                )
+                .await;
+            }
+        };
+
     if backups.is_empty() {
-        ctx.say(format!("There are currently no backups on {server_choice}"))
+        ctx.say(format!("There are currently no backups on {server_config}"))
             .await?;
         return Ok(());
     }
@@ -94,12 +99,11 @@ async fn list(
                 inline_code(b.uuid)
             ))
         })
-        .take(20)
         .collect::<Vec<anyhow::Result<String>>>();
 
     if !display_backups.iter().all(|b| b.is_ok()) {
         ctx.say(format!(
-            "Failed to parse creation date for backups from {server_choice}!"
+            "Failed to parse creation date for backups from {server_config}!"
         ))
         .await?;
         return Ok(());
@@ -108,16 +112,15 @@ async fn list(
     let display_backups = display_backups
         .into_iter()
         .filter_map(|b| b.ok())
-        .collect::<Vec<String>>()
-        .join("\n\n");
+        .collect::<Vec<String>>();
 
     let embed = default_embed(ctx.author())
-        .title(format!("Backup List for {} {server_choice}", guild.name))
-        .description(display_backups);
+        .title(format!("Backup List for {} {server_config}", guild.name))
+        .field("Count", display_backups.len().to_string(), false);
 
     let embed = maybe_set_guild_thumbnail(embed, &guild);
 
-    ctx.send(CreateReply::default().embed(embed)).await?;
+    send_paginated(ctx, embed, display_backups, 5, "\n\n").await?;
 
     Ok(())
 }
@@ -126,8 +129,11 @@ async fn list(
 #[poise::command(slash_command, guild_only = true)]
 async fn details(
     ctx: AppContext<'_>,
-    #[description = "Choose a server."] server_choice: ServerChoice,
-    #[description = "The ID of the backup you want to get the details of. You can get the ID from the list subcommand."]
+    #[description = "Choose a server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+    #[description = "The backup you want to get the details of."]
+    #[autocomplete = "autocomplete_backup"]
     backup_id: String,
 ) -> anyhow::Result<()> {
     let guild = ctx
@@ -135,6 +141,12 @@ async fn details(
         .await
         .context("Failed to fetch the guild this interaction was created in")?;
 
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
     let uuid = match Uuid::from_str(backup_id.as_str()) {
         Ok(uuid) => uuid,
         Err(e) => {
@@ -144,7 +156,7 @@ async fn details(
 
     let backup = match PteroClient::backup_details(
         &ctx.data().config.pterodactyl,
-        ctx.data().config.minecraft.get(server_choice),
+        server_config,
         uuid,
     )
     .await
@@ -152,7 +164,7 @@ async fn details(
         Ok(backup) => backup,
         Err(e) => {
             return respond_error(
-                format!("Failed to get backup with uuid {uuid} from {server_choice}"),
+                format!("Failed to get backup with uuid {uuid} from {server_config}"),
                 e,
                 &ctx,
             )
@@ -189,7 +201,7 @@ async fn details(
     };
 
     let embed = default_embed(ctx.author())
-        .title(format!("Backup details for {} {server_choice}", guild.name))
+        .title(format!("Backup details for {} {server_config}", guild.name))
         .field("Name", backup.name, false)
         .field("UUID", inline_code(backup.uuid), false)
         .field("Size", display_bytes(backup.bytes), false)
@@ -209,7 +221,9 @@ async fn details(
 #[poise::command(slash_command, guild_only = true, track_edits)]
 async fn create(
     ctx: AppContext<'_>,
-    #[description = "Choose a server."] server_choice: ServerChoice,
+    #[description = "Choose a server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
     #[description = "The name of the backup."] backup_name: Option<String>,
     #[description = "Wether the backup should be locked."] locked: Option<bool>,
 ) -> anyhow::Result<()> {
@@ -218,11 +232,15 @@ async fn create(
         .await
         .context("Failed to fetch the guild this interaction was created in")?;
 
-    let server_config = ctx.data().config.minecraft.get(server_choice);
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
 
     if server_config.backup_limit == 0 {
         ctx.say(format!(
-            "{} {server_choice} does not allow backups",
+            "{} {server_config} does not allow backups",
             guild.name
         ));
         return Ok(());
@@ -233,20 +251,20 @@ async fn create(
     if backups.len() == server_config.backup_limit as usize {
         if !has_unlocked_backups(&backups) {
             ctx.say(format!(
-                "{} {server_choice} does not have any unlocked backups to replace!",
+                "{} {server_config} does not have any unlocked backups to replace!",
                 guild.name
             ));
             return Ok(());
         }
 
-        handle_replace_oldest_backup(ctx, &guild, server_choice, backups, backup_name, locked)
+        handle_replace_oldest_backup(ctx, &guild, server_config, backups, backup_name, locked)
             .await?;
         return Ok(());
     }
 
     let handle = ctx
         .say(format!(
-            "Creating backup for {} {server_choice}...",
+            "Creating backup for {} {server_config}...",
             guild.name
         ))
         .await?;
@@ -265,12 +283,12 @@ async fn create(
     if created.completed_at.is_some() {
         let duration = calculate_completion_seconds(&created).unwrap();
         content = format!(
-            "Successfully created backup on {} {server_choice}. This took {} seconds!",
+            "Successfully created backup on {} {server_config}. This took {} seconds!",
             guild.name, duration
         );
     } else {
         content = format!(
-            "Successfully created backup on {} {server_choice} but failed to wait for completion!",
+            "Successfully created backup on {} {server_config} but failed to wait for completion!",
             guild.name
         );
     }
@@ -286,8 +304,11 @@ async fn create(
 #[poise::command(slash_command, guild_only = true)]
 async fn delete(
     ctx: AppContext<'_>,
-    #[description = "Choose a server."] server_choice: ServerChoice,
-    #[description = "The ID of the backup you want to delete. You can get the ID from the list subcommand."]
+    #[description = "Choose a server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+    #[description = "The backup you want to delete."]
+    #[autocomplete = "autocomplete_backup"]
     backup_id: String,
 ) -> anyhow::Result<()> {
     ctx.defer().await?;
@@ -297,6 +318,12 @@ async fn delete(
         .await
         .context("Failed to fetch the guild this interaction was created in")?;
 
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
     let uuid = match Uuid::from_str(backup_id.as_str()) {
         Ok(uuid) => uuid,
         Err(e) => {
@@ -306,16 +333,10 @@ async fn delete(
 
     let mut response = String::new();
 
-    match PteroClient::delete_backup(
-        &ctx.data().config.pterodactyl,
-        ctx.data().config.minecraft.get(server_choice),
-        uuid,
-    )
-    .await
-    {
+    match PteroClient::delete_backup(&ctx.data().config.pterodactyl, server_config, uuid).await {
         Ok(_) => {
             ctx.say(format!(
-                "Successfully deleted backup from {} {server_choice}.",
+                "Successfully deleted backup from {} {server_config}.",
                 guild.name
             ))
             .await?;
@@ -325,7 +346,7 @@ async fn delete(
         Err(e) => {
             respond_error(
                 format!(
-                    "Failed to delete backup {} from {} {server_choice}",
+                    "Failed to delete backup {} from {} {server_config}",
                     inline_code(uuid),
                     guild.name
                 ),
@@ -337,10 +358,112 @@ async fn delete(
     }
 }
 
+/// Lock a backup so it cannot be deleted.
+#[poise::command(slash_command, guild_only = true)]
+async fn lock(
+    ctx: AppContext<'_>,
+    #[description = "Choose a server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+    #[description = "The backup you want to lock."]
+    #[autocomplete = "autocomplete_backup"]
+    backup_id: String,
+) -> anyhow::Result<()> {
+    set_backup_locked(ctx, server_choice, backup_id, true).await
+}
+
+/// Unlock a backup so it can be deleted or replaced again.
+#[poise::command(slash_command, guild_only = true)]
+async fn unlock(
+    ctx: AppContext<'_>,
+    #[description = "Choose a server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+    #[description = "The backup you want to unlock."]
+    #[autocomplete = "autocomplete_backup"]
+    backup_id: String,
+) -> anyhow::Result<()> {
+    set_backup_locked(ctx, server_choice, backup_id, false).await
+}
+
+async fn set_backup_locked(
+    ctx: AppContext<'_>,
+    server_choice: String,
+    backup_id: String,
+    locked: bool,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let guild = ctx
+        .partial_guild()
+        .await
+        .context("Failed to fetch the guild this interaction was created in")?;
+
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
+    let uuid = match Uuid::from_str(backup_id.as_str()) {
+        Ok(uuid) => uuid,
+        Err(e) => {
+            return respond_error("Failed to parse UUID", e, &ctx).await;
+        }
+    };
+
+    let ptero_config = &ctx.data().config.pterodactyl;
+    let action = if locked { "lock" } else { "unlock" };
+
+    let backup = match PteroClient::backup_details(ptero_config, server_config, uuid).await {
+        Ok(backup) => backup,
+        Err(e) => {
+            return respond_error(
+                format!("Failed to get backup with uuid {uuid} from {server_config}"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
+
+    if backup.is_locked == locked {
+        ctx.say(format!(
+            "Backup {} on {} {server_config} is already {action}ed.",
+            inline_code(backup.name),
+            guild.name
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    if let Err(e) = PteroClient::toggle_backup_lock(ptero_config, server_config, uuid).await {
+        return respond_error(
+            format!(
+                "Failed to {action} backup {} on {} {server_config}",
+                inline_code(uuid),
+                guild.name
+            ),
+            e,
+            &ctx,
+        )
+        .await;
+    }
+
+    ctx.say(format!(
+        "Successfully {action}ed backup {} on {} {server_config}.",
+        inline_code(backup.name),
+        guild.name
+    ))
+    .await?;
+
+    Ok(())
+}
+
 async fn handle_replace_oldest_backup(
     ctx: AppContext<'_>,
     guild: &PartialGuild,
-    server_choice: ServerChoice,
+    server_config: &ServerConfig,
     backup_list: Vec<Backup>,
     backup_name: Option<String>,
     locked: Option<bool>,
@@ -349,7 +472,7 @@ async fn handle_replace_oldest_backup(
 
     let buttons = confirm_cancel_component();
     let reply = CreateReply::default().components(buttons).content(format!(
-        "This command will delete the oldest backup for {} {server_choice} because the backup limit is reached for this server. Are you sure you want to continue? This cannot be undone!",
+        "This command will delete the oldest backup for {} {server_config} because the backup limit is reached for this server. Are you sure you want to continue? This cannot be undone!",
         guild.name
     ));
 
@@ -370,7 +493,7 @@ async fn handle_replace_oldest_backup(
             let response = CreateInteractionResponse::UpdateMessage(
                 CreateInteractionResponseMessage::new()
                     .content(format!(
-                        "Cancelled creating a backup for {} {server_choice}!",
+                        "Cancelled creating a backup for {} {server_config}!",
                         guild.name
                     ))
                     .components(vec![]),
@@ -383,7 +506,7 @@ async fn handle_replace_oldest_backup(
         if custom_id.as_str() == "confirm" {
             if let Err(e) = delete_oldest_non_locked_backup(
                 &ctx.data().config.pterodactyl,
-                ctx.data().config.minecraft.get(server_choice),
+                server_config,
                 &backup_list,
             )
             .await
@@ -391,7 +514,7 @@ async fn handle_replace_oldest_backup(
                 let response = CreateInteractionResponse::UpdateMessage(
                     CreateInteractionResponseMessage::new()
                         .content(format!(
-                            "Failed to delete the oldest backup from {} {server_choice}!",
+                            "Failed to delete the oldest backup from {} {server_config}!",
                             guild.name
                         ))
                         .components(vec![]),
@@ -404,7 +527,7 @@ async fn handle_replace_oldest_backup(
             let response = CreateInteractionResponse::UpdateMessage(
                 CreateInteractionResponseMessage::new()
                     .content(format!(
-                        "Creating backup for {} {server_choice}...",
+                        "Creating backup for {} {server_config}...",
                         guild.name
                     ))
                     .components(vec![]),
@@ -414,7 +537,7 @@ async fn handle_replace_oldest_backup(
 
             let created = match PteroClient::create_backup_and_wait(
                 &ctx.data().config.pterodactyl,
-                ctx.data().config.minecraft.get(server_choice),
+                server_config,
                 backup_name.clone(),
                 locked,
                 ctx.author(),
@@ -426,14 +549,14 @@ async fn handle_replace_oldest_backup(
                     tracing::error!(
                         "Failed to create backup on {} {}: {e}",
                         guild.name,
-                        server_choice
+                        server_config
                     );
 
                     collector
                         .edit_response(
                             &ctx,
                             EditInteractionResponse::new().content(format!(
-                                "Failed to create a new backup for {} {server_choice}!",
+                                "Failed to create a new backup for {} {server_config}!",
                                 guild.name
                             )),
                         )
@@ -448,12 +571,12 @@ async fn handle_replace_oldest_backup(
             if created.completed_at.is_some() {
                 let duration = calculate_completion_seconds(&created).unwrap();
                 content = format!(
-                    "Successfully deleted the oldest backup and created a new one on {} {server_choice}. This took {} seconds!",
+                    "Successfully deleted the oldest backup and created a new one on {} {server_config}. This took {} seconds!",
                     guild.name, duration
                 );
             } else {
                 content = format!(
-                    "Successfully deleted the oldest backup and created a new one on {} {server_choice} but failed to wait for completion!",
+                    "Successfully deleted the oldest backup and created a new one on {} {server_config} but failed to wait for completion!",
                     guild.name
                 );
             }
@@ -474,18 +597,16 @@ async fn delete_oldest_non_locked_backup(
     server_config: &ServerConfig,
     backups: &[Backup],
 ) -> anyhow::Result<()> {
-    let server_choice = ServerChoice::try_from(server_config)?;
-
     let mut backups = backups
         .iter()
         .filter(|&b| !b.is_locked)
         .collect::<Vec<&Backup>>();
 
     if backups.is_empty() {
-        anyhow::bail!("There are no non-locked backups on {server_choice}")
+        anyhow::bail!("There are no non-locked backups on {server_config}")
     }
 
-    backups.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    backups.sort_by_key(|b| b.created_at);
 
     let first = backups.first().context("There is no backup to delete")?;
 
@@ -7,6 +7,7 @@ use url::Url;
 use crate::error::respond_error;
 use crate::util::builder::default_embed;
 use crate::util::format::display_time;
+use crate::util::paginate::send_paginated;
 use crate::util::random_utils::{maybe_set_guild_thumbnail, sort_player_list};
 use crate::Context as AppContext;
 
@@ -112,14 +113,15 @@ async fn members(ctx: AppContext<'_>) -> anyhow::Result<()> {
 
     let member_names = get_member_names_per_role(&ctx, &partial_guild, &members_role_id).await?;
 
-    let embed = default_embed(ctx.author())
-        .title("Info Members")
-        .description(member_names.join("\n"))
-        .field("Member Count", member_names.len().to_string(), false);
+    let embed = default_embed(ctx.author()).title("Info Members").field(
+        "Member Count",
+        member_names.len().to_string(),
+        false,
+    );
 
     let embed = maybe_set_guild_thumbnail(embed, &partial_guild);
 
-    ctx.send(CreateReply::default().embed(embed)).await?;
+    send_paginated(ctx, embed, member_names, 25, "\n").await?;
 
     Ok(())
 }
@@ -135,14 +137,15 @@ async fn admins(ctx: AppContext<'_>) -> anyhow::Result<()> {
 
     let admin_names = get_member_names_per_role(&ctx, &partial_guild, &admin_role_id).await?;
 
-    let embed = default_embed(ctx.author())
-        .title("Info Admins")
-        .description(admin_names.join("\n"))
-        .field("Admin Count", admin_names.len().to_string(), false);
+    let embed = default_embed(ctx.author()).title("Info Admins").field(
+        "Admin Count",
+        admin_names.len().to_string(),
+        false,
+    );
 
     let embed = maybe_set_guild_thumbnail(embed, &partial_guild);
 
-    ctx.send(CreateReply::default().embed(embed)).await?;
+    send_paginated(ctx, embed, admin_names, 25, "\n").await?;
 
     Ok(())
 }
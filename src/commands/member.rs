@@ -2,16 +2,20 @@ use anyhow::Context;
 use chrono::{offset::LocalResult, DateTime, TimeZone, Utc};
 use poise::{serenity_prelude as serenity, CreateReply};
 
-use serenity::User;
+use serenity::{CreateMessage, User};
 use uuid::Uuid;
 
 use crate::{
+    commands::whitelist::{
+        add_remove_whitelist, apply_access_policy, build_whitelist_report_embed, is_all_success,
+    },
     database::model::member::{CreateMember, MemberModelController, UpdateMember},
     error::respond_error,
     util::{
-        builder::default_embed,
+        builder::{default_embed, log_embed},
         format::{display, display_time, escape_markdown, fdisplay, inline_code},
         mojang::MojangAPI,
+        paginate::send_paginated,
         random_utils::{maybe_set_guild_thumbnail, sort_player_list},
     },
     Context as AppContext,
@@ -20,7 +24,7 @@ use crate::{
 #[poise::command(
     slash_command,
     guild_only = true,
-    subcommands("list", "info", "add", "update", "remove"),
+    subcommands("list", "info", "add", "update", "promote", "remove"),
     subcommand_required
 )]
 pub async fn member(_: AppContext<'_>) -> anyhow::Result<()> {
@@ -63,11 +67,11 @@ async fn list(ctx: AppContext<'_>) -> anyhow::Result<()> {
 
     let embed = default_embed(ctx.author())
         .title(format!("Memberlist for {}", guild.name))
-        .description(member_names.join("\n"));
+        .field("Member Count", member_names.len().to_string(), false);
 
     let embed = maybe_set_guild_thumbnail(embed, &guild);
 
-    ctx.send(CreateReply::default().embed(embed)).await?;
+    send_paginated(ctx, embed, member_names, 25, "\n").await?;
 
     Ok(())
 }
@@ -136,6 +140,11 @@ async fn info(
         .field("Last Updated At", display_time(mc_member.updated_at), false)
         .field("Trial Member", display_trial, false);
 
+    let embed = match mc_member.departed_at {
+        Some(departed_at) => embed.field("Departed At", display_time(departed_at), false),
+        None => embed,
+    };
+
     ctx.send(CreateReply::default().embed(embed)).await?;
 
     Ok(())
@@ -181,6 +190,11 @@ async fn add(
         }
     };
 
+    let names = profiles
+        .iter()
+        .map(|p| p.name.clone())
+        .collect::<Vec<String>>();
+
     let create_member = CreateMember {
         discord_id: user.id,
         trial_member,
@@ -190,12 +204,9 @@ async fn add(
 
     match MemberModelController::create(&ctx.data().db_pool, create_member).await {
         Ok(_) => {
-            ctx.say(format!(
-                "Successfully added {} to the Memberlist.",
-                fdisplay(&user)
-            ))
-            .await?;
-            Ok(())
+            let content = format!("Successfully added {} to the Memberlist.", fdisplay(&user));
+            respond_with_access_results(&ctx, content, names, Vec::new(), Vec::new(), trial_member)
+                .await
         }
         Err(e) => {
             if e.to_string() == "Unique constraint violation" {
@@ -258,6 +269,29 @@ async fn update(
         None
     };
 
+    let previous_uuids = match MemberModelController::get_by_id(&ctx.data().db_pool, &user.id).await
+    {
+        Ok(Some(member)) => member.minecraft_uuids,
+        Ok(None) => {
+            ctx.say(format!(
+                "{} is not a member in the database.",
+                fdisplay(&user)
+            ))
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            return respond_error(
+                format!("Failed to get {} from the database", display(&user)),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
+
+    let access_changed = trial_member.is_some() || minecraft_uuids.is_some();
+
     let update_member = UpdateMember {
         discord_id: user.id,
         trial_member,
@@ -266,13 +300,32 @@ async fn update(
     };
 
     match MemberModelController::update(&ctx.data().db_pool, update_member).await {
-        Ok(_) => {
-            ctx.say(format!(
-                "Successfully updated {} in the database.",
-                fdisplay(&user)
-            ))
-            .await?;
-            Ok(())
+        Ok(member) => {
+            let content = format!("Successfully updated {} in the database.", fdisplay(&user));
+
+            if !access_changed {
+                ctx.say(content).await?;
+                return Ok(());
+            }
+
+            let removed_uuids = previous_uuids
+                .into_iter()
+                .filter(|uuid| !member.minecraft_uuids.contains(uuid))
+                .collect::<Vec<Uuid>>();
+
+            let (names, mut unresolved) = resolve_names(&member.minecraft_uuids).await;
+            let (removed_names, removed_unresolved) = resolve_names(&removed_uuids).await;
+            unresolved.extend(removed_unresolved);
+
+            respond_with_access_results(
+                &ctx,
+                content,
+                names,
+                removed_names,
+                unresolved,
+                member.trial_member,
+            )
+            .await
         }
         Err(e) => {
             respond_error(
@@ -285,6 +338,95 @@ async fn update(
     }
 }
 
+/// Promote a trial member to a full member.
+#[poise::command(slash_command, guild_only = true)]
+async fn promote(
+    ctx: AppContext<'_>,
+    #[description = "The trial Member to promote."] user: User,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let db_pool = &ctx.data().db_pool;
+    let roles = &ctx.data().config.roles;
+
+    let Some(mc_member) = MemberModelController::get_by_id(db_pool, &user.id).await? else {
+        ctx.say(format!(
+            "{} is not a member in the database.",
+            fdisplay(&user)
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    if !mc_member.trial_member {
+        ctx.say(format!("{} is not a trial member.", fdisplay(&user)))
+            .await?;
+        return Ok(());
+    }
+
+    let update_member = UpdateMember {
+        discord_id: user.id,
+        trial_member: Some(false),
+        minecraft_uuids: None,
+        member_since: None,
+    };
+
+    if let Err(e) = MemberModelController::update(db_pool, update_member).await {
+        return respond_error(
+            format!("Failed to update {} in the database", display(&user)),
+            e,
+            &ctx,
+        )
+        .await;
+    }
+
+    let Some(guild_id) = ctx.guild_id() else {
+        anyhow::bail!("Failed to get guild id.");
+    };
+
+    let mut content = format!(
+        "Successfully promoted {} to a full member.",
+        fdisplay(&user)
+    );
+
+    let role_result = match guild_id.member(&ctx, user.id).await {
+        Ok(member) => match member.remove_role(&ctx, roles.trial).await {
+            Ok(_) => member.add_roles(&ctx, &[roles.member, roles.members]).await,
+            Err(e) => Err(e),
+        },
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = role_result {
+        tracing::error!("Failed to swap the roles of {}: {e}", display(&user));
+        content.push_str(" Failed to swap their roles!");
+    }
+
+    let embed = log_embed()
+        .title("Member Promoted")
+        .description(format!(
+            "{} was promoted to a full member by {}.",
+            fdisplay(&user),
+            fdisplay(ctx.author())
+        ))
+        .field("Trial Since", display_time(mc_member.member_since), false);
+
+    if let Err(e) = ctx
+        .data()
+        .config
+        .channels
+        .member_log
+        .send_message(&ctx, CreateMessage::new().embed(embed))
+        .await
+    {
+        tracing::error!("Failed to log the promotion of {}: {e}", display(&user));
+    }
+
+    let (names, unresolved) = resolve_names(&mc_member.minecraft_uuids).await;
+
+    respond_with_access_results(&ctx, content, names, Vec::new(), unresolved, false).await
+}
+
 #[poise::command(slash_command, guild_only = true)]
 async fn remove(
     ctx: AppContext<'_>,
@@ -313,6 +455,125 @@ async fn remove(
     }
 }
 
+/// Looks up the current IGNs of the accounts. Accounts that could not be looked up are returned
+/// separately, so one failed lookup does not hide the results of the others.
+async fn resolve_names(uuids: &[Uuid]) -> (Vec<String>, Vec<Uuid>) {
+    let mut names = Vec::new();
+    let mut unresolved = Vec::new();
+
+    for uuid in uuids {
+        match MojangAPI::get_profile_from_uuid(uuid).await {
+            Ok(profile) => names.push(profile.name),
+            Err(e) => {
+                tracing::warn!("Failed to get the IGN of {uuid} from the Mojang API: {e:#}");
+                unresolved.push(*uuid);
+            }
+        }
+    }
+
+    (names, unresolved)
+}
+
+/// Applies the server access policy to the member's accounts, removes accounts that are no longer
+/// connected to the member from all servers and reports the results.
+async fn respond_with_access_results(
+    ctx: &AppContext<'_>,
+    content: String,
+    names: Vec<String>,
+    removed_names: Vec<String>,
+    unresolved: Vec<Uuid>,
+    trial_member: bool,
+) -> anyhow::Result<()> {
+    let rcon = &ctx.data().rcon;
+    let configs = &ctx.data().config.minecraft;
+
+    let mut embeds = Vec::new();
+    let mut all_success = true;
+
+    if !unresolved.is_empty() {
+        all_success = false;
+
+        let uuids = unresolved
+            .iter()
+            .map(|uuid| inline_code(uuid.to_string()))
+            .collect::<Vec<String>>()
+            .join("\n");
+
+        embeds.push(
+            default_embed(ctx.author())
+                .title("Unresolved Accounts")
+                .description(format!(
+                    "The IGNs of these accounts could not be looked up, so their whitelist was not updated:\n{uuids}"
+                )),
+        );
+    }
+
+    for name in names {
+        let results = match apply_access_policy(name.as_str(), trial_member, rcon, configs).await {
+            Ok(results) => results,
+            Err(e) => {
+                return respond_error(
+                    format!("{content} But something went wrong updating the whitelist of {name}!"),
+                    e,
+                    ctx,
+                )
+                .await;
+            }
+        };
+
+        all_success &=
+            is_all_success(&results) && configs.iter().all(|config| config.allows(trial_member));
+
+        embeds.push(build_whitelist_report_embed(
+            ctx.author(),
+            format!("Whitelist Results for {name}"),
+            &results,
+        ));
+    }
+
+    for name in removed_names {
+        let results = match add_remove_whitelist(name.as_str(), rcon, configs, false).await {
+            Ok(results) => results,
+            Err(e) => {
+                return respond_error(
+                    format!(
+                        "{content} But something went wrong removing {name} from the whitelist!"
+                    ),
+                    e,
+                    ctx,
+                )
+                .await;
+            }
+        };
+
+        all_success &= is_all_success(&results);
+
+        embeds.push(build_whitelist_report_embed(
+            ctx.author(),
+            format!("Whitelist Results for {name}"),
+            &results,
+        ));
+    }
+
+    let reply = if all_success {
+        CreateReply::default().content(format!(
+            "{content} Their whitelist was updated on all servers."
+        ))
+    } else {
+        // a reply can hold at most 10 embeds
+        embeds
+            .into_iter()
+            .take(10)
+            .fold(CreateReply::default().content(content), |reply, embed| {
+                reply.embed(embed)
+            })
+    };
+
+    ctx.send(reply).await?;
+
+    Ok(())
+}
+
 fn parse_date_string(date_str: String) -> anyhow::Result<DateTime<Utc>> {
     let split = date_str
         .split('-')
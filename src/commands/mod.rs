@@ -1,11 +1,18 @@
 pub mod animal;
+pub mod application;
 pub mod backup;
 pub mod help;
 pub mod info;
 pub mod mcskin;
 pub mod member;
+pub mod online;
+pub mod op;
+pub mod playtime;
+pub mod rolepanel;
 pub mod roletoggle;
 pub mod run;
+pub mod seen;
+pub mod server;
 pub mod status;
 pub mod todo;
 pub mod whitelist;
@@ -0,0 +1,87 @@
+use futures_util::future::join_all;
+use poise::CreateReply;
+use pterodactyl_api::client::ServerState;
+
+use crate::{
+    commands::status::get_online_players,
+    config::ServerConfig,
+    util::{
+        builder::default_embed,
+        format::{escape_markdown, truncate},
+        pterodactyl::{DisplayServerState, PteroClient},
+        random_utils::sort_player_list,
+    },
+    Context as AppContext, Data,
+};
+
+/// Show who is online on all servers.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn online(ctx: AppContext<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let data = ctx.data();
+    let servers = data.config.minecraft.iter().collect::<Vec<&ServerConfig>>();
+
+    let results = join_all(
+        servers
+            .iter()
+            .map(|server_config| display_online_players(data, server_config)),
+    )
+    .await;
+
+    let total = results.iter().map(|(count, _)| count).sum::<usize>();
+
+    // an embed can hold at most 25 fields
+    let fields = servers
+        .iter()
+        .zip(results)
+        .take(25)
+        .map(|(server_config, (count, players))| {
+            (format!("{server_config} ({count})"), players, false)
+        });
+
+    let embed = default_embed(ctx.author())
+        .title("Online Players")
+        .description(format!("**{total}** players online"))
+        .fields(fields);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Returns the number of players online and the field value listing them.
+async fn display_online_players(data: &Data, server_config: &ServerConfig) -> (usize, String) {
+    let state = match PteroClient::server_state(&data.config.pterodactyl, server_config).await {
+        Ok(state) => state,
+        Err(e) => {
+            tracing::warn!("Failed to get the server state of {server_config}: {e}");
+            return (0, "Unknown".to_string());
+        }
+    };
+
+    if state != ServerState::Running {
+        return (0, format!("*{}*", DisplayServerState(state)));
+    }
+
+    let players = match get_online_players(&data.rcon, server_config).await {
+        Ok(players) => players,
+        Err(e) => {
+            tracing::warn!("Failed to get the players online on {server_config}: {e:#}");
+            return (0, "Failed to get the player list".to_string());
+        }
+    };
+
+    if players.is_empty() {
+        return (0, "Nobody is online.".to_string());
+    }
+
+    let mut names = players
+        .into_iter()
+        .map(|p| escape_markdown(p.name))
+        .collect::<Vec<String>>();
+
+    sort_player_list(&mut names);
+
+    (names.len(), truncate(&names.join(", "), 1024))
+}
@@ -0,0 +1,377 @@
+use std::{str::FromStr, time::Duration};
+
+use anyhow::Context;
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ComponentInteractionCollector, CreateInteractionResponse, CreateInteractionResponseMessage,
+    EditInteractionResponse,
+};
+
+use crate::{
+    commands::run::is_interaction_from_admin,
+    commands::whitelist::{
+        apply_list_diffs, build_whitelist_diff_embed, build_whitelist_report_embed,
+        compute_list_diffs, get_member_by_ign, is_all_success, OpResult, SyncedList, WhitelistDiff,
+        WhitelistResultElement,
+    },
+    config::{PterodactylConfig, ServerConfig},
+    error::respond_error,
+    util::{
+        autocomplete::autocomplete_server,
+        builder::default_embed,
+        format::escape_markdown,
+        paginate::send_paginated,
+        pterodactyl::PteroClient,
+        random_utils::{confirm_cancel_component, sort_player_list},
+        rcon::{run_rcon_command, RconManager},
+    },
+    Context as AppContext,
+};
+
+/// Manage operators on the servers that allow them.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("add", "remove", "list", "sync"),
+    subcommand_required
+)]
+pub async fn op(_: AppContext<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Make a player operator on all servers that allow operators.
+#[poise::command(slash_command, guild_only = true)]
+async fn add(
+    ctx: AppContext<'_>,
+    #[description = "The IGN of the player to op."] ign: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage operators!").await?;
+        return Ok(());
+    }
+
+    if ign.trim().is_empty() {
+        ctx.say("IGN cannot be empty!").await?;
+        return Ok(());
+    }
+
+    let member = match get_member_by_ign(&ctx.data().db_pool, &ign).await {
+        Ok(member) => member,
+        Err(e) => {
+            return respond_error(
+                format!("Failed to look up the member status of {ign}"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
+
+    let Some(member) = member.filter(|member| member.departed_at.is_none()) else {
+        ctx.say(format!(
+            "{} is not a member, only members can be made operator!",
+            escape_markdown(ign.as_str())
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let operators = Operators {
+        ptero_config: &ctx.data().config.pterodactyl,
+    };
+
+    let configs = ctx
+        .data()
+        .config
+        .minecraft
+        .iter()
+        .filter(|config| operators.includes(config, member.trial_member))
+        .collect::<Vec<&ServerConfig>>();
+
+    if configs.is_empty() {
+        ctx.say(format!(
+            "There are no servers that allow {} to be operator!",
+            escape_markdown(ign.as_str())
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let results = add_remove_op(&ign, &ctx.data().rcon, configs, true).await;
+
+    if is_all_success(&results) {
+        ctx.say(format!(
+            "Successfully made `{ign}` operator on all servers they have operator access to."
+        ))
+        .await?;
+    } else {
+        let embed = build_whitelist_report_embed(
+            ctx.author(),
+            format!("Operator Results for {ign}"),
+            &results,
+        );
+        ctx.send(CreateReply::default().embed(embed)).await?;
+    }
+
+    Ok(())
+}
+
+/// Remove operator status from a player on all servers.
+#[poise::command(slash_command, guild_only = true)]
+async fn remove(
+    ctx: AppContext<'_>,
+    #[description = "The IGN of the player to deop."] ign: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage operators!").await?;
+        return Ok(());
+    }
+
+    if ign.trim().is_empty() {
+        ctx.say("IGN cannot be empty!").await?;
+        return Ok(());
+    }
+
+    let results = add_remove_op(
+        &ign,
+        &ctx.data().rcon,
+        ctx.data().config.minecraft.iter(),
+        false,
+    )
+    .await;
+
+    if is_all_success(&results) {
+        ctx.say(format!(
+            "Successfully removed operator status from `{ign}` on all servers."
+        ))
+        .await?;
+    } else {
+        let embed = build_whitelist_report_embed(
+            ctx.author(),
+            format!("Operator Results for {ign}"),
+            &results,
+        );
+        ctx.send(CreateReply::default().embed(embed)).await?;
+    }
+
+    Ok(())
+}
+
+/// List the operators of a server.
+#[poise::command(slash_command, guild_only = true)]
+async fn list(
+    ctx: AppContext<'_>,
+    #[description = "The server to get the operators from."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let Some(server) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
+    let mut operators =
+        match PteroClient::operator_list(&ctx.data().config.pterodactyl, server).await {
+            Ok(operators) => operators,
+            Err(e) => {
+                return respond_error(format!("Failed to get the operators of {server}"), e, &ctx)
+                    .await;
+            }
+        };
+
+    if operators.is_empty() {
+        ctx.say(format!("There are no operators on {server}!"))
+            .await?;
+        return Ok(());
+    }
+
+    sort_player_list(&mut operators);
+
+    let count = operators.len();
+    let display = operators
+        .into_iter()
+        .map(escape_markdown)
+        .collect::<Vec<String>>();
+
+    let embed = default_embed(ctx.author())
+        .title(format!("{server} Operators"))
+        .field("Count", count.to_string(), false);
+
+    send_paginated(ctx, embed, display, 25, "\n").await?;
+
+    Ok(())
+}
+
+/// Op members on servers that allow operators and remove all operators everywhere else.
+#[poise::command(slash_command, guild_only = true)]
+async fn sync(ctx: AppContext<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage operators!").await?;
+        return Ok(());
+    }
+
+    let operators = Operators {
+        ptero_config: &ctx.data().config.pterodactyl,
+    };
+
+    let diffs = match compute_list_diffs(
+        &ctx.data().db_pool,
+        &ctx.data().config.minecraft,
+        &operators,
+    )
+    .await
+    {
+        Ok(diffs) => diffs,
+        Err(e) => {
+            return respond_error("Failed to compute the operator changes", e, &ctx).await;
+        }
+    };
+
+    if diffs.iter().all(WhitelistDiff::is_empty) {
+        ctx.say("The operators on all servers are already in sync with the member database.")
+            .await?;
+        return Ok(());
+    }
+
+    let embed = build_whitelist_diff_embed(
+        default_embed(ctx.author()).title("Operator Sync Preview"),
+        &diffs,
+    );
+
+    let reply = CreateReply::default()
+        .embed(embed)
+        .components(confirm_cancel_component());
+
+    ctx.send(reply).await?;
+
+    if let Some(collector) = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(Duration::from_secs(120))
+        .await
+        .filter(move |c| {
+            c.data.custom_id.as_str() == "confirm" || c.data.custom_id.as_str() == "cancel"
+        })
+    {
+        if collector.data.custom_id.as_str() == "cancel" {
+            let response = CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content("Cancelled the operator sync!")
+                    .components(vec![]),
+            );
+
+            collector.create_response(&ctx, response).await?;
+            return Ok(());
+        }
+
+        let response = CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .content("Applying operator changes...")
+                .components(vec![]),
+        );
+
+        collector.create_response(&ctx, response).await?;
+
+        let results = apply_list_diffs(
+            &ctx.data().rcon,
+            &ctx.data().config.minecraft,
+            &diffs,
+            &operators,
+        )
+        .await;
+
+        let embed = build_whitelist_diff_embed(
+            default_embed(ctx.author()).title("Operator Sync Results"),
+            &results,
+        );
+
+        collector
+            .edit_response(
+                &ctx,
+                EditInteractionResponse::new()
+                    .content("Finished applying operator changes.")
+                    .embed(embed),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Ops or deops the player on the given servers.
+async fn add_remove_op<'a>(
+    ign: &str,
+    rcon: &RconManager,
+    configs: impl IntoIterator<Item = &'a ServerConfig>,
+    add: bool,
+) -> Vec<WhitelistResultElement> {
+    let mut results = Vec::new();
+
+    for config in configs {
+        let command = if add {
+            format!("op {ign}")
+        } else {
+            format!("deop {ign}")
+        };
+
+        let response = run_rcon_command(rcon, config, vec![command])
+            .await
+            .into_iter()
+            .next();
+
+        let op = match response {
+            Some(Ok(Some(response))) => OpResult::from_str(&response).unwrap_or(OpResult::Fail),
+            _ => OpResult::Fail,
+        };
+
+        results.push(WhitelistResultElement {
+            server: config.to_string(),
+            whitelist: None,
+            op: Some(op),
+        });
+    }
+
+    results
+}
+
+/// Members should be operator on the servers flagged as `operator` they have access to and nobody
+/// should be operator on the other servers.
+pub struct Operators<'a> {
+    pub ptero_config: &'a PterodactylConfig,
+}
+
+impl SyncedList for Operators<'_> {
+    fn includes(&self, config: &ServerConfig, trial_member: bool) -> bool {
+        config.operator && config.allows(trial_member)
+    }
+
+    async fn fetch(&self, config: &ServerConfig) -> anyhow::Result<Vec<String>> {
+        PteroClient::operator_list(self.ptero_config, config)
+            .await
+            .context(format!("Failed to get the operators of {config}"))
+    }
+
+    fn add_command(&self, ign: &str) -> String {
+        format!("op {ign}")
+    }
+
+    fn remove_command(&self, ign: &str) -> String {
+        format!("deop {ign}")
+    }
+
+    fn succeeded(&self, response: &str) -> bool {
+        matches!(
+            OpResult::from_str(response),
+            Ok(OpResult::Success | OpResult::Already)
+        )
+    }
+}
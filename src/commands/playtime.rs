@@ -0,0 +1,339 @@
+use chrono::{DateTime, Utc};
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{CreateEmbed, User};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{
+    config::MinecraftConfig,
+    database::model::{
+        member::MemberModelController,
+        player_session::{PlayerSessionModelController, ServerPlaytime},
+    },
+    error::respond_error,
+    util::{
+        autocomplete::autocomplete_server,
+        builder::default_embed,
+        format::{display_duration, escape_markdown},
+        mojang::MojangAPI,
+    },
+    Context as AppContext,
+};
+
+const LEADERBOARD_SIZE: i64 = 10;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum PlaytimePeriod {
+    #[name = "Last day"]
+    Day,
+    #[name = "Last week"]
+    Week,
+    #[name = "Last 30 days"]
+    Month,
+    #[name = "All time"]
+    All,
+}
+
+impl PlaytimePeriod {
+    fn since(&self) -> Option<DateTime<Utc>> {
+        let duration = match self {
+            Self::Day => chrono::Duration::days(1),
+            Self::Week => chrono::Duration::weeks(1),
+            Self::Month => chrono::Duration::days(30),
+            Self::All => return None,
+        };
+
+        Some(Utc::now() - duration)
+    }
+}
+
+/// Show how long players have been playing on the servers.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("player", "member", "leaderboard"),
+    subcommand_required
+)]
+pub async fn playtime(_: AppContext<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Show the playtime of a minecraft player.
+#[poise::command(slash_command, guild_only = true)]
+async fn player(
+    ctx: AppContext<'_>,
+    #[description = "The minecraft name of the player."] name: String,
+    #[description = "The period to count. Defaults to all time."] period: Option<PlaytimePeriod>,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let db_pool = &ctx.data().db_pool;
+    let name = name.trim();
+
+    let uuid = match resolve_player(db_pool, name).await {
+        Ok(Some(uuid)) => uuid,
+        Ok(None) => {
+            ctx.say(format!(
+                "There is no minecraft player called {}!",
+                escape_markdown(name)
+            ))
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            return respond_error("Failed to get the player from the database", e, &ctx).await;
+        }
+    };
+
+    let period = period.unwrap_or(PlaytimePeriod::All);
+
+    let playtimes = match PlayerSessionModelController::playtime(db_pool, &[uuid], period.since())
+        .await
+    {
+        Ok(playtimes) => playtimes,
+        Err(e) => {
+            return respond_error("Failed to get the playtime from the database", e, &ctx).await;
+        }
+    };
+
+    let discord = match MemberModelController::get_by_minecraft_uuid(db_pool, &uuid).await? {
+        Some(member) => format!("<@{}>", member.discord_id),
+        None => "Not a member".to_string(),
+    };
+
+    let embed = build_playtime_embed(
+        default_embed(ctx.author()).title(format!("Playtime of {}", escape_markdown(name))),
+        &ctx.data().config.minecraft,
+        &playtimes,
+        period,
+    )
+    .field("Discord", discord, false);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Show the playtime of a member across all of their minecraft accounts.
+#[poise::command(slash_command, guild_only = true)]
+async fn member(
+    ctx: AppContext<'_>,
+    #[description = "The member."] user: User,
+    #[description = "The period to count. Defaults to all time."] period: Option<PlaytimePeriod>,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let db_pool = &ctx.data().db_pool;
+
+    let Some(member) = MemberModelController::get_by_id(db_pool, &user.id).await? else {
+        ctx.say(format!(
+            "{} is not a member!",
+            escape_markdown(user.name.as_str())
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let period = period.unwrap_or(PlaytimePeriod::All);
+
+    let playtimes = match PlayerSessionModelController::playtime(
+        db_pool,
+        &member.minecraft_uuids,
+        period.since(),
+    )
+    .await
+    {
+        Ok(playtimes) => playtimes,
+        Err(e) => {
+            return respond_error("Failed to get the playtime from the database", e, &ctx).await;
+        }
+    };
+
+    let accounts = display_accounts(ctx, &member.minecraft_uuids).await?;
+    let username = user.global_name.clone().unwrap_or(user.name.clone());
+
+    let embed = build_playtime_embed(
+        default_embed(ctx.author())
+            .title(format!("Playtime of {}", escape_markdown(username)))
+            .thumbnail(user.face()),
+        &ctx.data().config.minecraft,
+        &playtimes,
+        period,
+    )
+    .field("Accounts", accounts, false);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Show the players with the most playtime.
+#[poise::command(slash_command, guild_only = true)]
+async fn leaderboard(
+    ctx: AppContext<'_>,
+    #[description = "Only count playtime on this server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: Option<String>,
+    #[description = "The period to count. Defaults to the last 30 days."] period: Option<
+        PlaytimePeriod,
+    >,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let db_pool = &ctx.data().db_pool;
+
+    let server_config = match server_choice {
+        Some(server_choice) => match ctx.data().config.minecraft.get(&server_choice) {
+            Some(server_config) => Some(server_config),
+            None => {
+                ctx.say(format!("There is no server called {server_choice}!"))
+                    .await?;
+                return Ok(());
+            }
+        },
+        None => None,
+    };
+
+    let period = period.unwrap_or(PlaytimePeriod::Month);
+
+    let entries = match PlayerSessionModelController::leaderboard(
+        db_pool,
+        server_config.map(|s| s.server_name.as_str()),
+        period.since(),
+        LEADERBOARD_SIZE,
+    )
+    .await
+    {
+        Ok(entries) => entries,
+        Err(e) => {
+            return respond_error("Failed to get the leaderboard from the database", e, &ctx).await;
+        }
+    };
+
+    let title = match server_config {
+        Some(server_config) => format!("Playtime Leaderboard {server_config}"),
+        None => "Playtime Leaderboard".to_string(),
+    };
+
+    if entries.is_empty() {
+        ctx.say(format!(
+            "There is no recorded playtime ({}).",
+            display_period(period)
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let mut lines = Vec::with_capacity(entries.len());
+
+    for (i, entry) in entries.iter().enumerate() {
+        let discord =
+            match MemberModelController::get_by_minecraft_uuid(db_pool, &entry.minecraft_uuid)
+                .await?
+            {
+                Some(member) => format!(" (<@{}>)", member.discord_id),
+                None => String::new(),
+            };
+
+        lines.push(format!(
+            "**{}.** {}{discord}: {}",
+            i + 1,
+            escape_markdown(entry.name.as_str()),
+            display_duration(entry.playtime)
+        ));
+    }
+
+    let embed = default_embed(ctx.author())
+        .title(title)
+        .description(lines.join("\n"))
+        .field("Period", display_period(period), false);
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// Finds the UUID of a player by the name they last played under. Players that renamed since
+/// their last session are only found through mojang.
+pub async fn resolve_player(db_pool: &PgPool, name: &str) -> anyhow::Result<Option<Uuid>> {
+    if let Some(uuid) = PlayerSessionModelController::find_by_name(db_pool, name)
+        .await?
+        .into_iter()
+        .next()
+    {
+        return Ok(Some(uuid));
+    }
+
+    Ok(MojangAPI::get_profile_from_username(name)
+        .await
+        .ok()
+        .map(|profile| profile.id))
+}
+
+fn build_playtime_embed(
+    embed: CreateEmbed,
+    configs: &MinecraftConfig,
+    playtimes: &[ServerPlaytime],
+    period: PlaytimePeriod,
+) -> CreateEmbed {
+    let total = playtimes
+        .iter()
+        .fold(chrono::Duration::zero(), |total, p| total + p.playtime);
+
+    let per_server = if playtimes.is_empty() {
+        "No recorded sessions.".to_string()
+    } else {
+        playtimes
+            .iter()
+            .map(|p| {
+                let server = configs
+                    .get(&p.server_name)
+                    .map(|s| s.to_string())
+                    .unwrap_or(p.server_name.clone());
+
+                format!("{server}: {}", display_duration(p.playtime))
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    };
+
+    embed
+        .field("Total", format!("**{}**", display_duration(total)), false)
+        .field("Servers", per_server, false)
+        .field("Period", display_period(period), false)
+}
+
+/// Displays the last known names of the accounts, from the sessions or mojang.
+async fn display_accounts(ctx: AppContext<'_>, uuids: &[Uuid]) -> anyhow::Result<String> {
+    if uuids.is_empty() {
+        return Ok("None".to_string());
+    }
+
+    let sessions =
+        PlayerSessionModelController::get_last_sessions(&ctx.data().db_pool, uuids).await?;
+
+    let mut names = Vec::with_capacity(uuids.len());
+
+    for uuid in uuids {
+        let name = match sessions.iter().find(|s| s.minecraft_uuid == *uuid) {
+            Some(session) => session.name.clone(),
+            None => match MojangAPI::get_profile_from_uuid(uuid).await {
+                Ok(profile) => profile.name,
+                Err(_) => uuid.to_string(),
+            },
+        };
+
+        names.push(escape_markdown(name));
+    }
+
+    Ok(names.join(", "))
+}
+
+fn display_period(period: PlaytimePeriod) -> &'static str {
+    match period {
+        PlaytimePeriod::Day => "last day",
+        PlaytimePeriod::Week => "last week",
+        PlaytimePeriod::Month => "last 30 days",
+        PlaytimePeriod::All => "all time",
+    }
+}
@@ -0,0 +1,602 @@
+use std::{fmt::Display, str::FromStr};
+
+use poise::serenity_prelude as serenity;
+use serenity::{
+    ButtonStyle, ComponentInteraction, ComponentInteractionDataKind, CreateActionRow, CreateButton,
+    CreateEmbed, CreateMessage, CreateSelectMenu, CreateSelectMenuKind, CreateSelectMenuOption,
+    EditMessage, GuildChannel, Role, RoleId,
+};
+
+use crate::{
+    commands::run::is_interaction_from_admin,
+    database::model::role_panel::{
+        CreateRolePanel, RolePanel, RolePanelModelController, RolePanelRole,
+    },
+    error::respond_error,
+    util::{
+        autocomplete::autocomplete_role_panel,
+        builder::{default_embed, log_embed},
+        format::{escape_markdown, inline_code},
+        paginate::send_paginated,
+        random_utils::respond_ephemeral,
+    },
+    Context as AppContext, Data,
+};
+
+pub const ROLE_PANEL_PREFIX: &str = "role_panel";
+
+/// Discord allows at most 25 buttons or select menu options per message.
+const MAX_ROLES_PER_PANEL: usize = 25;
+
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum PanelStyle {
+    Buttons,
+    Select,
+}
+
+impl Display for PanelStyle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Buttons => write!(f, "buttons"),
+            Self::Select => write!(f, "select"),
+        }
+    }
+}
+
+impl FromStr for PanelStyle {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "buttons" => Ok(PanelStyle::Buttons),
+            "select" => Ok(PanelStyle::Select),
+            _ => anyhow::bail!("{} is not a valid panel style", s),
+        }
+    }
+}
+
+/// Manage self-assign role panels.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("create", "add_role", "remove_role", "post", "list", "delete"),
+    subcommand_required
+)]
+pub async fn rolepanel(_: AppContext<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Create a new role panel.
+#[poise::command(slash_command, guild_only = true)]
+async fn create(
+    ctx: AppContext<'_>,
+    #[description = "The title of the panel."] title: String,
+    #[description = "Whether members pick roles with buttons or a select menu."] style: PanelStyle,
+    #[description = "The text shown above the roles."] description: Option<String>,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage role panels!").await?;
+        return Ok(());
+    }
+
+    if title.trim().is_empty() {
+        ctx.say("Title cannot be empty!").await?;
+        return Ok(());
+    }
+
+    let create_panel = CreateRolePanel {
+        title,
+        description,
+        style,
+        created_by: ctx.author().id,
+    };
+
+    let panel = match RolePanelModelController::create(&ctx.data().db_pool, create_panel).await {
+        Ok(panel) => panel,
+        Err(e) => {
+            return respond_error("Failed to create the role panel in the database", e, &ctx).await;
+        }
+    };
+
+    ctx.say(format!(
+        "Created role panel {}. Add roles with `/rolepanel add_role` and post it with `/rolepanel post`.",
+        inline_code(format!("#{}", panel.id))
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// Add a role to a panel or change its label.
+#[poise::command(slash_command, guild_only = true)]
+async fn add_role(
+    ctx: AppContext<'_>,
+    #[description = "The role panel."]
+    #[autocomplete = "autocomplete_role_panel"]
+    panel: i32,
+    #[description = "The role members can assign themselves."] role: Role,
+    #[description = "The label shown on the panel. Defaults to the role name."] label: Option<
+        String,
+    >,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage role panels!").await?;
+        return Ok(());
+    }
+
+    let db_pool = &ctx.data().db_pool;
+
+    let Some(panel) = RolePanelModelController::get_by_id(db_pool, panel).await? else {
+        ctx.say(format!("There is no role panel #{panel}!")).await?;
+        return Ok(());
+    };
+
+    if role.managed || role.id.get() == role.guild_id.get() {
+        ctx.say(format!(
+            "{} cannot be assigned by members!",
+            escape_markdown(role.name.as_str())
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let roles = RolePanelModelController::roles(db_pool, panel.id).await?;
+
+    if roles.len() >= MAX_ROLES_PER_PANEL && !roles.iter().any(|r| r.role_id == role.id) {
+        ctx.say(format!(
+            "A role panel can hold at most {MAX_ROLES_PER_PANEL} roles!"
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let label = label
+        .filter(|label| !label.trim().is_empty())
+        .unwrap_or(role.name.clone());
+
+    let panel_role = RolePanelRole {
+        role_id: role.id,
+        label: label.chars().take(80).collect(),
+    };
+
+    if let Err(e) = RolePanelModelController::add_role(db_pool, panel.id, panel_role).await {
+        return respond_error("Failed to add the role to the panel", e, &ctx).await;
+    }
+
+    let content = format!(
+        "Added {} to role panel #{}.",
+        escape_markdown(role.name.as_str()),
+        panel.id
+    );
+
+    respond_with_refresh(&ctx, &panel, content).await
+}
+
+/// Remove a role from a panel.
+#[poise::command(slash_command, guild_only = true)]
+async fn remove_role(
+    ctx: AppContext<'_>,
+    #[description = "The role panel."]
+    #[autocomplete = "autocomplete_role_panel"]
+    panel: i32,
+    #[description = "The role to remove."] role: Role,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage role panels!").await?;
+        return Ok(());
+    }
+
+    let db_pool = &ctx.data().db_pool;
+
+    let Some(panel) = RolePanelModelController::get_by_id(db_pool, panel).await? else {
+        ctx.say(format!("There is no role panel #{panel}!")).await?;
+        return Ok(());
+    };
+
+    match RolePanelModelController::remove_role(db_pool, panel.id, role.id).await {
+        Ok(true) => {}
+        Ok(false) => {
+            ctx.say(format!(
+                "{} is not on role panel #{}!",
+                escape_markdown(role.name.as_str()),
+                panel.id
+            ))
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            return respond_error("Failed to remove the role from the panel", e, &ctx).await;
+        }
+    }
+
+    let content = format!(
+        "Removed {} from role panel #{}.",
+        escape_markdown(role.name.as_str()),
+        panel.id
+    );
+
+    respond_with_refresh(&ctx, &panel, content).await
+}
+
+/// Post a role panel, replacing the previously posted message.
+#[poise::command(slash_command, guild_only = true)]
+async fn post(
+    ctx: AppContext<'_>,
+    #[description = "The role panel."]
+    #[autocomplete = "autocomplete_role_panel"]
+    panel: i32,
+    #[description = "The channel to post in. Defaults to this channel."]
+    #[channel_types("Text")]
+    channel: Option<GuildChannel>,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage role panels!").await?;
+        return Ok(());
+    }
+
+    let db_pool = &ctx.data().db_pool;
+
+    let Some(panel) = RolePanelModelController::get_by_id(db_pool, panel).await? else {
+        ctx.say(format!("There is no role panel #{panel}!")).await?;
+        return Ok(());
+    };
+
+    let roles = RolePanelModelController::roles(db_pool, panel.id).await?;
+
+    if roles.is_empty() {
+        ctx.say(format!(
+            "Role panel #{} has no roles yet! Add some with `/rolepanel add_role`.",
+            panel.id
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let channel_id = channel.map(|c| c.id).unwrap_or(ctx.channel_id());
+
+    let message = CreateMessage::new()
+        .embed(build_panel_embed(&panel, &roles))
+        .components(build_panel_components(&panel, &roles));
+
+    let message = match channel_id.send_message(&ctx, message).await {
+        Ok(message) => message,
+        Err(e) => {
+            return respond_error(
+                format!("Failed to post the panel in <#{channel_id}>"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
+
+    if let (Some(old_channel), Some(old_message)) = (panel.channel_id, panel.message_id) {
+        if let Err(e) = old_channel.delete_message(&ctx, old_message).await {
+            tracing::warn!(
+                "Failed to delete the old message of role panel {}: {e}",
+                panel.id
+            );
+        }
+    }
+
+    RolePanelModelController::set_message(db_pool, panel.id, channel_id, message.id).await?;
+
+    ctx.say(format!(
+        "Posted role panel #{} in <#{channel_id}>.",
+        panel.id
+    ))
+    .await?;
+
+    Ok(())
+}
+
+/// List all role panels.
+#[poise::command(slash_command, guild_only = true)]
+async fn list(ctx: AppContext<'_>) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    let db_pool = &ctx.data().db_pool;
+
+    let panels = match RolePanelModelController::get_all(db_pool).await {
+        Ok(panels) => panels,
+        Err(e) => {
+            return respond_error("Failed to get the role panels from the database", e, &ctx).await;
+        }
+    };
+
+    if panels.is_empty() {
+        ctx.say("There are no role panels.").await?;
+        return Ok(());
+    }
+
+    let mut display = Vec::with_capacity(panels.len());
+
+    for panel in &panels {
+        let roles = RolePanelModelController::roles(db_pool, panel.id).await?;
+
+        let display_roles = if roles.is_empty() {
+            "No roles".to_string()
+        } else {
+            roles
+                .iter()
+                .map(|r| format!("<@&{}>", r.role_id))
+                .collect::<Vec<String>>()
+                .join(", ")
+        };
+
+        let posted = match (panel.channel_id, panel.message_id) {
+            (Some(channel_id), Some(message_id)) => message_id.link(channel_id, ctx.guild_id()),
+            _ => "Not posted".to_string(),
+        };
+
+        display.push(format!(
+            "**#{} {}**\n**Style**: {}\n**Posted**: {posted}\n**Roles**: {display_roles}",
+            panel.id,
+            escape_markdown(panel.title.as_str()),
+            panel.style
+        ));
+    }
+
+    let embed = default_embed(ctx.author()).title("Role Panels");
+
+    send_paginated(ctx, embed, display, 10, "\n\n").await?;
+
+    Ok(())
+}
+
+/// Delete a role panel and its posted message.
+#[poise::command(slash_command, guild_only = true)]
+async fn delete(
+    ctx: AppContext<'_>,
+    #[description = "The role panel."]
+    #[autocomplete = "autocomplete_role_panel"]
+    panel: i32,
+) -> anyhow::Result<()> {
+    ctx.defer_ephemeral().await?;
+
+    if !is_interaction_from_admin(&ctx).await? {
+        ctx.say("Only admins can manage role panels!").await?;
+        return Ok(());
+    }
+
+    let db_pool = &ctx.data().db_pool;
+
+    let Some(panel) = RolePanelModelController::get_by_id(db_pool, panel).await? else {
+        ctx.say(format!("There is no role panel #{panel}!")).await?;
+        return Ok(());
+    };
+
+    if let Err(e) = RolePanelModelController::delete(db_pool, panel.id).await {
+        return respond_error("Failed to delete the role panel from the database", e, &ctx).await;
+    }
+
+    if let (Some(channel_id), Some(message_id)) = (panel.channel_id, panel.message_id) {
+        if let Err(e) = channel_id.delete_message(&ctx, message_id).await {
+            tracing::warn!(
+                "Failed to delete the message of role panel {}: {e}",
+                panel.id
+            );
+        }
+    }
+
+    ctx.say(format!("Deleted role panel #{}.", panel.id))
+        .await?;
+
+    Ok(())
+}
+
+/// Handles the buttons and select menus of posted panels. Button ids look like
+/// `role_panel:<panel id>:<role id>` and select menu ids like `role_panel:<panel id>`.
+pub async fn handle_role_panel_interaction(
+    component: &ComponentInteraction,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    let mut parts = component.data.custom_id.split(':').skip(1);
+
+    let Some(Ok(panel_id)) = parts.next().map(str::parse::<i32>) else {
+        anyhow::bail!("Invalid role panel component {}", component.data.custom_id);
+    };
+
+    let Some(member) = component.member.clone() else {
+        return respond_ephemeral(component, ctx, "Role panels only work in the server!").await;
+    };
+
+    let roles = RolePanelModelController::roles(&data.db_pool, panel_id).await?;
+
+    // only roles that are still on the panel can be assigned, even if the message is outdated
+    let selected = match &component.data.kind {
+        ComponentInteractionDataKind::Button => {
+            let Some(Ok(role_id)) = parts.next().map(str::parse::<u64>) else {
+                anyhow::bail!("Invalid role panel button {}", component.data.custom_id);
+            };
+
+            let role_id = RoleId::new(role_id);
+
+            if !roles.iter().any(|r| r.role_id == role_id) {
+                return respond_ephemeral(component, ctx, "This role is no longer on the panel!")
+                    .await;
+            }
+
+            let has_role = member.roles.contains(&role_id);
+
+            let result = if has_role {
+                member.remove_role(ctx, role_id).await
+            } else {
+                member.add_role(ctx, role_id).await
+            };
+
+            let content = match (result, has_role) {
+                (Ok(_), true) => format!("Removed <@&{role_id}> from you."),
+                (Ok(_), false) => format!("Gave you <@&{role_id}>."),
+                (Err(e), _) => {
+                    tracing::error!("Failed to toggle role {role_id} from a role panel: {e}");
+                    format!("Failed to toggle <@&{role_id}>!")
+                }
+            };
+
+            return respond_ephemeral(component, ctx, content).await;
+        }
+        ComponentInteractionDataKind::StringSelect { values } => values
+            .iter()
+            .filter_map(|value| value.parse::<u64>().ok().map(RoleId::new))
+            .collect::<Vec<RoleId>>(),
+        _ => anyhow::bail!(
+            "Unexpected role panel component {}",
+            component.data.custom_id
+        ),
+    };
+
+    let mut added = Vec::new();
+    let mut removed = Vec::new();
+    let mut failed = Vec::new();
+
+    for role in &roles {
+        let wanted = selected.contains(&role.role_id);
+        let has_role = member.roles.contains(&role.role_id);
+
+        let result = match (wanted, has_role) {
+            (true, false) => member.add_role(ctx, role.role_id).await,
+            (false, true) => member.remove_role(ctx, role.role_id).await,
+            _ => continue,
+        };
+
+        match (result, wanted) {
+            (Ok(_), true) => added.push(format!("<@&{}>", role.role_id)),
+            (Ok(_), false) => removed.push(format!("<@&{}>", role.role_id)),
+            (Err(e), _) => {
+                tracing::error!(
+                    "Failed to update role {} from a role panel: {e}",
+                    role.role_id
+                );
+                failed.push(format!("<@&{}>", role.role_id));
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+
+    if !added.is_empty() {
+        lines.push(format!("**Added**: {}", added.join(", ")));
+    }
+
+    if !removed.is_empty() {
+        lines.push(format!("**Removed**: {}", removed.join(", ")));
+    }
+
+    if !failed.is_empty() {
+        lines.push(format!("**Failed**: {}", failed.join(", ")));
+    }
+
+    if lines.is_empty() {
+        lines.push("Your roles did not change.".to_string());
+    }
+
+    respond_ephemeral(component, ctx, lines.join("\n")).await
+}
+
+/// Edits the posted panel message to match the roles in the database and reports the outcome.
+async fn respond_with_refresh(
+    ctx: &AppContext<'_>,
+    panel: &RolePanel,
+    content: String,
+) -> anyhow::Result<()> {
+    let (Some(channel_id), Some(message_id)) = (panel.channel_id, panel.message_id) else {
+        ctx.say(content).await?;
+        return Ok(());
+    };
+
+    let roles = RolePanelModelController::roles(&ctx.data().db_pool, panel.id).await?;
+
+    let edit = EditMessage::new()
+        .embed(build_panel_embed(panel, &roles))
+        .components(build_panel_components(panel, &roles));
+
+    if let Err(e) = channel_id.edit_message(ctx, message_id, edit).await {
+        tracing::error!(
+            "Failed to update the message of role panel {}: {e}",
+            panel.id
+        );
+        ctx.say(format!(
+            "{content} Failed to update the posted panel, post it again with `/rolepanel post`."
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    ctx.say(format!("{content} The posted panel was updated."))
+        .await?;
+
+    Ok(())
+}
+
+fn build_panel_embed(panel: &RolePanel, roles: &[RolePanelRole]) -> CreateEmbed {
+    let hint = match panel.style {
+        PanelStyle::Buttons => "Click a button to toggle the role.",
+        PanelStyle::Select => "Select the roles you want, deselect the ones you don't.",
+    };
+
+    let description = match &panel.description {
+        Some(description) => format!("{description}\n\n{hint}"),
+        None => hint.to_string(),
+    };
+
+    let display_roles = roles
+        .iter()
+        .map(|r| format!("<@&{}>: {}", r.role_id, escape_markdown(r.label.as_str())))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    log_embed()
+        .title(panel.title.clone())
+        .description(description)
+        .field("Roles", display_roles, false)
+}
+
+fn build_panel_components(panel: &RolePanel, roles: &[RolePanelRole]) -> Vec<CreateActionRow> {
+    if roles.is_empty() {
+        return Vec::new();
+    }
+
+    match panel.style {
+        PanelStyle::Buttons => roles
+            .chunks(5)
+            .map(|chunk| {
+                let buttons = chunk
+                    .iter()
+                    .map(|r| {
+                        CreateButton::new(format!("{ROLE_PANEL_PREFIX}:{}:{}", panel.id, r.role_id))
+                            .label(r.label.clone())
+                            .style(ButtonStyle::Secondary)
+                    })
+                    .collect();
+
+                CreateActionRow::Buttons(buttons)
+            })
+            .collect(),
+        PanelStyle::Select => {
+            let options = roles
+                .iter()
+                .map(|r| CreateSelectMenuOption::new(r.label.clone(), r.role_id.to_string()))
+                .collect::<Vec<CreateSelectMenuOption>>();
+
+            let menu = CreateSelectMenu::new(
+                format!("{ROLE_PANEL_PREFIX}:{}", panel.id),
+                CreateSelectMenuKind::String { options },
+            )
+            .placeholder("Choose your roles")
+            .min_values(0)
+            .max_values(roles.len() as u8);
+
+            vec![CreateActionRow::SelectMenu(menu)]
+        }
+    }
+}
@@ -1,6 +1,10 @@
+use poise::serenity_prelude as serenity;
+use serenity::{Cache, Member};
+
 use crate::{
-    config::{ServerChoice, ServerConfig},
+    error::respond_error,
     util::{
+        autocomplete::autocomplete_server,
         format::{block_code, fdisplay},
         rcon::run_rcon_command,
     },
@@ -11,18 +15,26 @@ use crate::{
 #[poise::command(slash_command, guild_only = true)]
 pub async fn run(
     ctx: AppContext<'_>,
-    #[description = "Choose a server to run the command on."] server_choice: ServerChoice,
+    #[description = "Choose a server to run the command on."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
     #[description = "The command to run on the server."] command: String,
 ) -> anyhow::Result<()> {
     ctx.defer().await?;
 
-    if let ServerChoice::Smp = server_choice {
-        if !is_interaction_from_admin(&ctx).await? {
-            ctx.say("You must be an admin to run arbitrary commands on SMP!")
-                .await?;
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
 
-            return Ok(());
-        }
+    if server_config.admin_only && !is_interaction_from_admin(&ctx).await? {
+        ctx.say(format!(
+            "You must be an admin to run arbitrary commands on {server_config}!"
+        ))
+        .await?;
+
+        return Ok(());
     }
 
     if command.is_empty() {
@@ -30,21 +42,30 @@ pub async fn run(
         return Ok(());
     }
 
-    let response = run_rcon_command(
-        ctx.data().config.minecraft.get(server_choice),
-        vec![command],
-    )
-    .await?;
+    let response = run_rcon_command(&ctx.data().rcon, server_config, vec![command])
+        .await
+        .into_iter()
+        .next();
 
-    if response.len() != 1 {
+    let Some(response) = response else {
         ctx.say("Encountered unexpected response from the server.")
             .await?;
         return Ok(());
-    }
+    };
 
-    let mut response = response[0]
-        .clone()
-        .unwrap_or("Command ran successfully but there was no response.".to_string());
+    let mut response = match response {
+        Ok(response) => {
+            response.unwrap_or("Command ran successfully but there was no response.".to_string())
+        }
+        Err(e) => {
+            return respond_error(
+                format!("Failed to run the command on {server_config}"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
 
     if response.len() > 2000 {
         response.truncate(1950);
@@ -56,7 +77,7 @@ pub async fn run(
     Ok(())
 }
 
-async fn is_interaction_from_admin(ctx: &AppContext<'_>) -> anyhow::Result<bool> {
+pub async fn is_interaction_from_admin(ctx: &AppContext<'_>) -> anyhow::Result<bool> {
     let Some(member) = ctx.author_member().await else {
         return Err(anyhow::anyhow!(
             "Cannot get member from the interaction. Is user {} not a member of the server?",
@@ -64,7 +85,9 @@ async fn is_interaction_from_admin(ctx: &AppContext<'_>) -> anyhow::Result<bool>
         ));
     };
 
-    Ok(member
-        .permissions(ctx.cache())
-        .is_ok_and(|p| p.administrator()))
+    Ok(is_admin(&member, ctx.cache()))
+}
+
+pub fn is_admin(member: &Member, cache: &Cache) -> bool {
+    member.permissions(cache).is_ok_and(|p| p.administrator())
 }
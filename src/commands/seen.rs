@@ -0,0 +1,98 @@
+use poise::CreateReply;
+
+use crate::{
+    commands::playtime::resolve_player,
+    database::model::{
+        member::MemberModelController, player_session::PlayerSessionModelController,
+    },
+    error::respond_error,
+    util::{
+        builder::default_embed,
+        format::{display_duration, display_time, escape_markdown},
+    },
+    Context as AppContext,
+};
+
+/// Show when a player was last online.
+#[poise::command(slash_command, guild_only = true)]
+pub async fn seen(
+    ctx: AppContext<'_>,
+    #[description = "The minecraft name of the player."] ign: String,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let db_pool = &ctx.data().db_pool;
+    let ign = ign.trim();
+
+    let uuid = match resolve_player(db_pool, ign).await {
+        Ok(Some(uuid)) => uuid,
+        Ok(None) => {
+            ctx.say(format!(
+                "There is no minecraft player called {}!",
+                escape_markdown(ign)
+            ))
+            .await?;
+            return Ok(());
+        }
+        Err(e) => {
+            return respond_error("Failed to get the player from the database", e, &ctx).await;
+        }
+    };
+
+    let session = match PlayerSessionModelController::get_last_sessions(db_pool, &[uuid]).await {
+        Ok(sessions) => sessions.into_iter().next(),
+        Err(e) => {
+            return respond_error("Failed to get the sessions from the database", e, &ctx).await;
+        }
+    };
+
+    let Some(session) = session else {
+        ctx.say(format!(
+            "{} has never been seen on any of the servers.",
+            escape_markdown(ign)
+        ))
+        .await?;
+        return Ok(());
+    };
+
+    let server = ctx
+        .data()
+        .config
+        .minecraft
+        .get(&session.server_name)
+        .map(|s| s.to_string())
+        .unwrap_or(session.server_name.clone());
+
+    let embed = default_embed(ctx.author())
+        .title(format!(
+            "Last Seen {}",
+            escape_markdown(session.name.as_str())
+        ))
+        .thumbnail(format!("https://visage.surgeplay.com/face/256/{uuid}"))
+        .field("Server", server, false);
+
+    let embed = match session.left_at {
+        Some(left_at) => embed
+            .field("Status", "Offline", false)
+            .field("Last Online", display_time(left_at), false)
+            .field(
+                "Last Session",
+                display_duration(left_at - session.joined_at),
+                false,
+            ),
+        None => embed.field("Status", "Online", false).field(
+            "Online Since",
+            display_time(session.joined_at),
+            false,
+        ),
+    };
+
+    let embed = match MemberModelController::get_by_minecraft_uuid(db_pool, &uuid).await? {
+        Some(member) => embed.field("Discord", format!("<@{}>", member.discord_id), false),
+        None => embed,
+    };
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,234 @@
+use std::{fmt::Display, time::Duration};
+
+use poise::serenity_prelude as serenity;
+use poise::CreateReply;
+use pterodactyl_api::client::{PowerSignal, ServerState};
+use serenity::{
+    ComponentInteractionCollector, CreateInteractionResponse, CreateInteractionResponseMessage,
+    EditInteractionResponse,
+};
+
+use crate::{
+    commands::run::is_interaction_from_admin,
+    config::ServerConfig,
+    util::{
+        autocomplete::autocomplete_server,
+        pterodactyl::{DisplayServerState, PteroClient},
+        random_utils::confirm_cancel_component,
+    },
+    Context as AppContext,
+};
+
+#[derive(Debug, Copy, Clone, poise::ChoiceParameter)]
+enum PowerChoice {
+    Start,
+    Stop,
+    Restart,
+    Kill,
+}
+
+impl Display for PowerChoice {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PowerChoice::Start => write!(f, "start"),
+            PowerChoice::Stop => write!(f, "stop"),
+            PowerChoice::Restart => write!(f, "restart"),
+            PowerChoice::Kill => write!(f, "kill"),
+        }
+    }
+}
+
+impl From<PowerChoice> for PowerSignal {
+    fn from(choice: PowerChoice) -> Self {
+        match choice {
+            PowerChoice::Start => PowerSignal::Start,
+            PowerChoice::Stop => PowerSignal::Stop,
+            PowerChoice::Restart => PowerSignal::Restart,
+            PowerChoice::Kill => PowerSignal::Kill,
+        }
+    }
+}
+
+impl PowerChoice {
+    fn target_state(&self) -> ServerState {
+        match self {
+            PowerChoice::Start | PowerChoice::Restart => ServerState::Running,
+            PowerChoice::Stop | PowerChoice::Kill => ServerState::Offline,
+        }
+    }
+}
+
+/// Control the minecraft servers.
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("power"),
+    subcommand_required
+)]
+pub async fn server(_: AppContext<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Start, stop, restart or kill a minecraft server.
+#[poise::command(slash_command, guild_only = true)]
+async fn power(
+    ctx: AppContext<'_>,
+    #[description = "Choose a server."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+    #[description = "The power action to send to the server."] action: PowerChoice,
+) -> anyhow::Result<()> {
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
+    if !is_allowed_to_control_power(&ctx, server_config).await? {
+        ctx.say(format!(
+            "You are not allowed to control the power state of {server_config}!"
+        ))
+        .await?;
+        return Ok(());
+    }
+
+    let reply = CreateReply::default()
+        .components(confirm_cancel_component())
+        .content(format!(
+            "Are you sure you want to {action} {server_config}?"
+        ));
+
+    ctx.send(reply).await?;
+
+    if let Some(collector) = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(Duration::from_secs(120))
+        .await
+        .filter(move |c| {
+            c.data.custom_id.as_str() == "confirm" || c.data.custom_id.as_str() == "cancel"
+        })
+    {
+        if collector.data.custom_id.as_str() == "cancel" {
+            let response = CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content(format!("Cancelled sending {action} to {server_config}!"))
+                    .components(vec![]),
+            );
+
+            collector.create_response(&ctx, response).await?;
+            return Ok(());
+        }
+
+        let response = CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .content(format!("Sending {action} to {server_config}..."))
+                .components(vec![]),
+        );
+
+        collector.create_response(&ctx, response).await?;
+
+        if let Err(e) = PteroClient::send_power_signal(
+            &ctx.data().config.pterodactyl,
+            server_config,
+            action.into(),
+        )
+        .await
+        {
+            tracing::error!("{e:#}");
+
+            collector
+                .edit_response(
+                    &ctx,
+                    EditInteractionResponse::new()
+                        .content(format!("Failed to send {action} to {server_config}!")),
+                )
+                .await?;
+
+            return Ok(());
+        }
+
+        let content = match wait_for_state(&ctx, &collector, server_config, action).await {
+            Ok(true) => format!(
+                "{server_config} is now {}!",
+                DisplayServerState(action.target_state())
+            ),
+            Ok(false) => format!(
+                "Sent {action} to {server_config} but it did not become {} in time!",
+                DisplayServerState(action.target_state())
+            ),
+            Err(e) => {
+                tracing::error!("Failed to poll the server state of {server_config}: {e}");
+                format!("Sent {action} to {server_config} but failed to check the server state!")
+            }
+        };
+
+        collector
+            .edit_response(&ctx, EditInteractionResponse::new().content(content))
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Polls the server state until it settles on the state the action leads to and mirrors every
+/// intermediate state into the reply.
+async fn wait_for_state(
+    ctx: &AppContext<'_>,
+    collector: &serenity::ComponentInteraction,
+    server_config: &ServerConfig,
+    action: PowerChoice,
+) -> anyhow::Result<bool> {
+    let target = action.target_state();
+    let mut last_state = None;
+    let mut left_target = !matches!(action, PowerChoice::Restart);
+
+    for _ in 0..60 {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+
+        let state =
+            PteroClient::server_state(&ctx.data().config.pterodactyl, server_config).await?;
+
+        if state != target {
+            left_target = true;
+        }
+
+        if state == target && left_target {
+            return Ok(true);
+        }
+
+        if last_state != Some(state) {
+            collector
+                .edit_response(
+                    ctx,
+                    EditInteractionResponse::new().content(format!(
+                        "Sent {action} to {server_config}. The server is currently {}...",
+                        DisplayServerState(state)
+                    )),
+                )
+                .await?;
+
+            last_state = Some(state);
+        }
+    }
+
+    Ok(false)
+}
+
+async fn is_allowed_to_control_power(
+    ctx: &AppContext<'_>,
+    server_config: &ServerConfig,
+) -> anyhow::Result<bool> {
+    if is_interaction_from_admin(ctx).await? {
+        return Ok(true);
+    }
+
+    let Some(member) = ctx.author_member().await else {
+        return Ok(false);
+    };
+
+    Ok(server_config
+        .power_roles
+        .iter()
+        .any(|role| member.roles.contains(role)))
+}
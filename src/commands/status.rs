@@ -1,23 +1,83 @@
+use std::fmt::Display;
+
 use anyhow::Context;
-use poise::CreateReply;
+use chrono::Utc;
+use poise::{serenity_prelude as serenity, CreateReply};
 use pterodactyl_api::client::ServerState;
-use serde::Deserialize;
+use serenity::CreateEmbed;
+use uuid::Uuid;
 
 use crate::{
-    config::{Config, MinecraftConfig, ServerChoice},
+    config::ServerConfig,
+    database::model::server_metrics::ServerMetricsModelController,
     error::respond_error,
     util::{
-        builder::default_embed,
+        autocomplete::autocomplete_server,
+        builder::{default_embed, log_embed},
+        format::{escape_markdown, time, truncate, TimestampStyle},
+        graph::render_metrics_graph,
         pterodactyl::{DisplayServerState, PteroClient},
-        rcon::run_rcon_command,
+        rcon::{run_rcon_command, RconManager},
+        server_list_ping::ping_server,
     },
-    Context as AppContext,
+    Context as AppContext, Data,
 };
 
+#[derive(Debug, Clone, Copy, poise::ChoiceParameter)]
+pub enum MetricsWindow {
+    #[name = "Last hour"]
+    Hour,
+    #[name = "Last 6 hours"]
+    SixHours,
+    #[name = "Last day"]
+    Day,
+    #[name = "Last week"]
+    Week,
+    #[name = "Last 30 days"]
+    Month,
+}
+
+impl MetricsWindow {
+    pub fn duration(&self) -> chrono::Duration {
+        match self {
+            Self::Hour => chrono::Duration::hours(1),
+            Self::SixHours => chrono::Duration::hours(6),
+            Self::Day => chrono::Duration::days(1),
+            Self::Week => chrono::Duration::weeks(1),
+            Self::Month => chrono::Duration::days(30),
+        }
+    }
+}
+
+impl Display for MetricsWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Hour => write!(f, "last hour"),
+            Self::SixHours => write!(f, "last 6 hours"),
+            Self::Day => write!(f, "last day"),
+            Self::Week => write!(f, "last week"),
+            Self::Month => write!(f, "last 30 days"),
+        }
+    }
+}
+
+#[poise::command(
+    slash_command,
+    guild_only = true,
+    subcommands("current", "history", "graph"),
+    subcommand_required
+)]
+pub async fn status(_: AppContext<'_>) -> anyhow::Result<()> {
+    Ok(())
+}
+
+/// Show the current status of a server.
 #[poise::command(slash_command, guild_only = true)]
-pub async fn status(
+async fn current(
     ctx: AppContext<'_>,
-    #[description = "Choose a server to run the command on."] server_choice: ServerChoice,
+    #[description = "Choose a server to run the command on."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
 ) -> anyhow::Result<()> {
     ctx.defer().await?;
 
@@ -26,7 +86,11 @@ pub async fn status(
         .await
         .context("Failed to fetch the guild this interaction was created in")?;
 
-    let server_config = ctx.data().config.minecraft.get(server_choice);
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
 
     let server_state =
         match PteroClient::server_state(&ctx.data().config.pterodactyl, server_config).await {
@@ -35,7 +99,7 @@ pub async fn status(
                 return respond_error(
                     format!(
                         "Failed to get server state for {} from the pterodactyl API",
-                        server_choice
+                        server_config
                     ),
                     e,
                     &ctx,
@@ -47,30 +111,25 @@ pub async fn status(
     if server_state != ServerState::Running {
         ctx.say(format!(
             "{} is currently {}!",
-            server_choice,
+            server_config,
             DisplayServerState(server_state)
         ))
         .await?;
         return Ok(());
     }
 
-    let mc_status = match mc_status(&ctx.data().config.minecraft, server_choice).await {
+    let server_status = match ping_server(&server_config.host, server_config.port).await {
         Ok(status) => status,
         Err(e) => {
-            return respond_error(
-                format!("Failed to get status for {server_choice} from the mcstatus.io API"),
-                e,
-                &ctx,
-            )
-            .await;
+            return respond_error(format!("Failed to ping {server_config}"), e, &ctx).await;
         }
     };
 
-    let server_metrics = match get_server_metrics(server_choice, &ctx.data().config).await {
+    let server_metrics = match get_server_metrics(&ctx.data().rcon, server_config).await {
         Ok(metrics) => metrics,
         Err(e) => {
             return respond_error(
-                format!("Failed to get or calculate metrics for {server_choice}"),
+                format!("Failed to get or calculate metrics for {server_config}"),
                 e,
                 &ctx,
             )
@@ -78,15 +137,115 @@ pub async fn status(
         }
     };
 
-    let title = format!("{} {}", guild.name, server_choice);
+    let title = format!("{} {}", guild.name, server_config);
     let colour = calculate_embed_color(server_metrics.performance.mspt);
     let icon_url = guild.icon_url().unwrap_or_default();
 
-    let version = mc_status
+    let version = server_status
         .version
-        .map(|v| v.name_raw)
+        .as_ref()
+        .map(|v| v.name.clone())
         .unwrap_or("Unknown version".to_string());
 
+    let motd = server_status.motd();
+
+    let embed = default_embed(ctx.author())
+        .title(title)
+        .colour(colour)
+        .thumbnail(icon_url)
+        .field(
+            "Status",
+            format!("Online ({} ms)", server_status.latency.as_millis()),
+            false,
+        )
+        .field("Version", version, false);
+
+    let embed = add_metrics_fields(embed, &server_metrics);
+
+    let embed = if motd.is_empty() {
+        embed
+    } else {
+        embed.description(escape_markdown(motd))
+    };
+
+    ctx.send(CreateReply::default().embed(embed)).await?;
+
+    Ok(())
+}
+
+/// A server on the status board in `channels.server_info`. The player list is kept out of the embed
+/// so the board can shorten it when all embeds together would be too long for one message.
+pub struct StatusBoardEntry {
+    pub embed: CreateEmbed,
+    pub player_list: Option<String>,
+}
+
+/// Builds the entry of a server on the status board in `channels.server_info`.
+pub async fn build_status_board_entry(
+    data: &Data,
+    server_config: &ServerConfig,
+) -> StatusBoardEntry {
+    let embed = log_embed().title(server_config.to_string());
+
+    let server_state =
+        match PteroClient::server_state(&data.config.pterodactyl, server_config).await {
+            Ok(state) => state,
+            Err(e) => {
+                tracing::warn!("Failed to get the server state of {server_config}: {e}");
+                return StatusBoardEntry {
+                    embed: embed
+                        .colour(OFFLINE_COLOUR)
+                        .field("Status", "Unknown", false),
+                    player_list: None,
+                };
+            }
+        };
+
+    if server_state != ServerState::Running {
+        return StatusBoardEntry {
+            embed: embed.colour(OFFLINE_COLOUR).field(
+                "Status",
+                DisplayServerState(server_state).to_string(),
+                false,
+            ),
+            player_list: None,
+        };
+    }
+
+    match get_server_metrics(&data.rcon, server_config).await {
+        Ok(metrics) => {
+            let embed = embed
+                .colour(calculate_embed_color(metrics.performance.mspt))
+                .field("Status", "Online", false);
+
+            StatusBoardEntry {
+                embed: add_performance_fields(embed, &metrics),
+                player_list: Some(display_player_list(&metrics)),
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Failed to get the metrics of {server_config}: {e:#}");
+            StatusBoardEntry {
+                embed: embed.field("Status", "Online", false).field(
+                    "Metrics",
+                    "Failed to get the server metrics.",
+                    false,
+                ),
+                player_list: None,
+            }
+        }
+    }
+}
+
+fn add_metrics_fields(embed: CreateEmbed, server_metrics: &ServerMetrics) -> CreateEmbed {
+    add_performance_fields(embed, server_metrics).field(
+        "Playerlist",
+        truncate(&display_player_list(server_metrics), 1024),
+        false,
+    )
+}
+
+fn add_performance_fields(embed: CreateEmbed, server_metrics: &ServerMetrics) -> CreateEmbed {
     let performance = format!(
         "**{}** MSPT | **{}** TPS",
         server_metrics.performance.mspt, server_metrics.performance.tps
@@ -104,93 +263,255 @@ pub async fn status(
         server_metrics.players.count, server_metrics.players.max
     );
 
-    let player_list = if server_metrics.players.count == 0 {
+    embed
+        .field("Performance", performance, false)
+        .field("Hostile Mobcaps", mobcaps, false)
+        .field("Playercount", player_count, false)
+}
+
+fn display_player_list(server_metrics: &ServerMetrics) -> String {
+    if server_metrics.players.count == 0 {
         "There is currently nobody online.".to_string()
     } else {
         server_metrics.players.playerlist.join("\n")
+    }
+}
+
+/// Summarize the recorded performance of a server.
+#[poise::command(slash_command, guild_only = true)]
+async fn history(
+    ctx: AppContext<'_>,
+    #[description = "Choose a server to run the command on."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+    #[description = "The time window to summarize. Defaults to the last day."] window: Option<
+        MetricsWindow,
+    >,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
+    let window = window.unwrap_or(MetricsWindow::Day);
+    let since = Utc::now() - window.duration();
+
+    let summary = match ServerMetricsModelController::summary(
+        &ctx.data().db_pool,
+        &server_config.server_name,
+        since,
+    )
+    .await
+    {
+        Ok(summary) => summary,
+        Err(e) => {
+            return respond_error(
+                format!("Failed to get the recorded metrics for {server_config}"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
+
+    let Some(summary) = summary else {
+        ctx.say(format!(
+            "There are no recorded metrics for {server_config} in the {window}!"
+        ))
+        .await?;
+        return Ok(());
     };
 
+    let mspt = format!(
+        "min: **{}** | avg: **{:.1}** | max: **{}**",
+        summary.min_mspt, summary.avg_mspt, summary.max_mspt
+    );
+
+    let peak_players = format!(
+        "**{}** ({})",
+        summary.peak_players,
+        time(summary.peak_at, TimestampStyle::Relative)
+    );
+
     let embed = default_embed(ctx.author())
-        .title(title)
-        .colour(colour)
-        .thumbnail(icon_url)
-        .field("Status", "Online", false)
-        .field("Version", version, false)
-        .field("Performance", performance, false)
-        .field("Hostile Mobcaps", mobcaps, false)
-        .field("Playercount", player_count, false)
-        .field("Playerlist", player_list, false);
+        .title(format!("{server_config} Performance History"))
+        .colour(calculate_embed_color(summary.avg_mspt.round() as u32))
+        .description(format!(
+            "Since {} ({window})",
+            time(since, TimestampStyle::ShortDateTime)
+        ))
+        .field("MSPT", mspt, false)
+        .field("Average TPS", format!("**{:.1}**", summary.avg_tps), false)
+        .field("Peak Players", peak_players, false)
+        .field("Samples", summary.samples.to_string(), false);
 
     ctx.send(CreateReply::default().embed(embed)).await?;
 
     Ok(())
 }
 
-#[derive(Debug, Deserialize)]
-struct McStatusResponse {
-    version: Option<McStatusVersionResponse>,
-}
+/// Draw a graph of the recorded performance of a server.
+#[poise::command(slash_command, guild_only = true)]
+async fn graph(
+    ctx: AppContext<'_>,
+    #[description = "Choose a server to run the command on."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
+    #[description = "The time window to draw. Defaults to the last day."] window: Option<
+        MetricsWindow,
+    >,
+) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let Some(server_config) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
+    let window = window.unwrap_or(MetricsWindow::Day);
+    let since = Utc::now() - window.duration();
+
+    let samples = match ServerMetricsModelController::get_samples(
+        &ctx.data().db_pool,
+        &server_config.server_name,
+        since,
+    )
+    .await
+    {
+        Ok(samples) => samples,
+        Err(e) => {
+            return respond_error(
+                format!("Failed to get the recorded metrics for {server_config}"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
+
+    if samples.len() < 2 {
+        ctx.say(format!(
+            "There are not enough recorded metrics for {server_config} in the {window} to draw a graph!"
+        ))
+        .await?;
+        return Ok(());
+    }
 
-#[derive(Debug, Deserialize)]
-struct McStatusVersionResponse {
-    name_raw: String,
+    // gaps of more than two missed samples are drawn as downtime
+    let max_gap = ctx
+        .data()
+        .config
+        .metrics
+        .as_ref()
+        .and_then(|m| i64::try_from(m.interval_minutes * 3).ok())
+        .map(chrono::Duration::minutes);
+
+    let title = format!("{server_config} ({window})");
+
+    let png =
+        match tokio::task::spawn_blocking(move || render_metrics_graph(&title, &samples, max_gap))
+            .await
+            .map_err(anyhow::Error::from)
+            .and_then(|result| result)
+        {
+            Ok(png) => png,
+            Err(e) => {
+                return respond_error(
+                    format!("Failed to draw the graph for {server_config}"),
+                    e,
+                    &ctx,
+                )
+                .await;
+            }
+        };
+
+    let file_name = format!(
+        "{}-{}.png",
+        server_config.server_name,
+        Utc::now().timestamp()
+    );
+
+    let embed = default_embed(ctx.author())
+        .title(format!("{server_config} Performance Graph"))
+        .description(format!(
+            "Since {} ({window})",
+            time(since, TimestampStyle::ShortDateTime)
+        ))
+        .image(format!("attachment://{file_name}"));
+
+    ctx.send(
+        CreateReply::default()
+            .embed(embed)
+            .attachment(serenity::CreateAttachment::bytes(png, file_name)),
+    )
+    .await?;
+
+    Ok(())
 }
 
-struct Mobcap {
-    overworld: String,
-    the_nether: String,
-    the_end: String,
+pub struct Mobcap {
+    pub overworld: String,
+    pub the_nether: String,
+    pub the_end: String,
 }
 
-struct ServerPerformance {
-    mspt: u32,
-    tps: u32,
+pub struct ServerPerformance {
+    pub mspt: u32,
+    pub tps: u32,
 }
 
-struct PlayerMetrics {
-    count: u16,
-    max: u16,
-    playerlist: Vec<String>,
+pub struct PlayerMetrics {
+    pub count: u16,
+    pub max: u16,
+    pub playerlist: Vec<String>,
 }
 
-struct ServerMetrics {
-    mobcap: Mobcap,
-    performance: ServerPerformance,
-    players: PlayerMetrics,
+pub struct ServerMetrics {
+    pub mobcap: Mobcap,
+    pub performance: ServerPerformance,
+    pub players: PlayerMetrics,
 }
 
-async fn mc_status(
-    mc_config: &MinecraftConfig,
-    server_choice: ServerChoice,
-) -> anyhow::Result<McStatusResponse> {
-    reqwest::get(format!(
-        "https://api.mcstatus.io/v2/status/java/{}:{}",
-        mc_config.get(server_choice).host,
-        mc_config.get(server_choice).port
-    ))
-    .await?
-    .json::<McStatusResponse>()
-    .await
-    .context("Failed to parse status response from mcstatus.io API")
+/// Averages the last 100 tick times, which is what the MSPT of all metrics is based on.
+const PERFORMANCE_COMMAND: &str =
+    "script run reduce(system_info('server_last_tick_times'), _a+_, 0)/100";
+
+pub async fn get_server_performance(
+    rcon: &RconManager,
+    server_config: &ServerConfig,
+) -> anyhow::Result<ServerPerformance> {
+    let response = run_rcon_command(rcon, server_config, vec![PERFORMANCE_COMMAND])
+        .await
+        .pop()
+        .context(format!(
+            "No response to the performance script from {server_config}"
+        ))??
+        .context(format!(
+            "Empty response to the performance script from {server_config}"
+        ))?;
+
+    parse_performance(response)
 }
 
-async fn get_server_metrics(
-    server: ServerChoice,
-    config: &Config,
+pub async fn get_server_metrics(
+    rcon: &RconManager,
+    server_config: &ServerConfig,
 ) -> anyhow::Result<ServerMetrics> {
     let commands = vec![
         "execute in minecraft:overworld run script run get_mob_counts('monster')".to_string(),
         "execute in minecraft:the_nether run script run get_mob_counts('monster')".to_string(),
         "execute in minecraft:the_end run script run get_mob_counts('monster')".to_string(),
-        "script run reduce(system_info('server_last_tick_times'), _a+_, 0)/100".to_string(),
+        PERFORMANCE_COMMAND.to_string(),
         "list".to_string(),
     ];
 
-    let error_message = format!("Failed to execute the server metrics scripts on {server}");
+    let error_message = format!("Failed to execute the server metrics scripts on {server_config}");
 
-    let responses = run_rcon_command(config.minecraft.get(server), commands)
-        .await
-        .context(error_message.clone())?;
+    let responses = run_rcon_command(rcon, server_config, commands).await;
 
     if responses.len() != 5 {
         anyhow::bail!(error_message.clone())
@@ -199,7 +520,7 @@ async fn get_server_metrics(
     let mut safe_responses = Vec::new();
 
     for r in responses {
-        if let Some(r) = r {
+        if let Some(r) = r.context(error_message.clone())? {
             safe_responses.push(r)
         } else {
             anyhow::bail!(error_message);
@@ -223,6 +544,39 @@ async fn get_server_metrics(
     Ok(metrics)
 }
 
+pub struct OnlinePlayer {
+    pub name: String,
+    pub uuid: Uuid,
+}
+
+/// Lists the players on a server with their UUIDs, using `list uuids` so no Mojang lookups are
+/// needed.
+pub async fn get_online_players(
+    rcon: &RconManager,
+    server_config: &ServerConfig,
+) -> anyhow::Result<Vec<OnlinePlayer>> {
+    let response = run_rcon_command(rcon, server_config, vec!["list uuids"])
+        .await
+        .pop()
+        .context(format!("No response to list from {server_config}"))??
+        .context(format!("Empty response to list from {server_config}"))?;
+
+    parse_playerlist(response)?
+        .playerlist
+        .into_iter()
+        .map(|entry| {
+            let Some((name, uuid)) = entry.trim().rsplit_once(" (") else {
+                anyhow::bail!("Failed to parse list entry: {entry}");
+            };
+
+            Ok(OnlinePlayer {
+                name: name.to_string(),
+                uuid: uuid.trim_end_matches(')').parse()?,
+            })
+        })
+        .collect()
+}
+
 fn parse_performance(performance_response: String) -> anyhow::Result<ServerPerformance> {
     let split = performance_response.split(' ').collect::<Vec<&str>>();
 
@@ -307,7 +661,9 @@ fn parse_mobcaps(mobcap_responses: Vec<String>) -> anyhow::Result<Mobcap> {
     })
 }
 
-fn calculate_embed_color(mspt: u32) -> u32 {
+const OFFLINE_COLOUR: u32 = 9_807_270;
+
+pub fn calculate_embed_color(mspt: u32) -> u32 {
     match mspt {
         30..=39 => 16_769_536,
         40..=49 => 16_737_843,
@@ -1,20 +1,30 @@
-use std::{
-    net::{Ipv4Addr, SocketAddr},
-    ops::ControlFlow,
-    str::FromStr,
-};
+use std::{collections::HashSet, future::Future, ops::ControlFlow, str::FromStr, time::Duration};
 
 use anyhow::Context;
+use futures_util::{stream, StreamExt};
 use poise::{serenity_prelude as serenity, CreateReply};
-use rcon::Builder;
-use serenity::{CreateEmbed, User};
+use serenity::{
+    ComponentInteractionCollector, CreateEmbed, CreateInteractionResponse,
+    CreateInteractionResponseMessage, EditInteractionResponse, User,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::{
-    config::{Config, MinecraftConfig, ServerChoice, ServerConfig},
+    config::{MinecraftConfig, ServerConfig},
+    database::model::{
+        member::{MemberModelController, MinecraftMember},
+        player_session::PlayerSessionModelController,
+    },
     error::respond_error,
     util::{
-        builder::default_embed, format::escape_markdown, random_utils::sort_player_list,
-        rcon::run_rcon_command,
+        autocomplete::autocomplete_server,
+        builder::default_embed,
+        format::escape_markdown,
+        mojang::MojangAPI,
+        paginate::send_paginated,
+        random_utils::{confirm_cancel_component, sort_player_list},
+        rcon::{run_rcon_command, RconManager},
     },
     Context as AppContext,
 };
@@ -23,7 +33,7 @@ use crate::{
 #[poise::command(
     slash_command,
     guild_only = true,
-    subcommands("add", "remove", "list"),
+    subcommands("add", "remove", "list", "sync"),
     subcommand_required
 )]
 pub async fn whitelist(_: AppContext<'_>) -> anyhow::Result<()> {
@@ -43,26 +53,55 @@ async fn add(
         return Ok(());
     }
 
-    let results =
-        match add_remove_whitelist(ign.as_str(), ctx.data().config.minecraft.clone(), true).await {
-            Ok(r) => r,
-            Err(e) => {
-                return respond_error(
-                    format!("Something went wrong trying to add {ign} to the whitelist!"),
-                    e,
-                    &ctx,
-                )
-                .await;
-            }
-        };
+    let trial_member = match is_trial_ign(&ctx.data().db_pool, &ign).await {
+        Ok(trial_member) => trial_member,
+        Err(e) => {
+            return respond_error(
+                format!("Failed to look up the member status of {ign}"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
 
-    if is_all_success(&results) {
+    let results = match apply_access_policy(
+        ign.as_str(),
+        trial_member,
+        &ctx.data().rcon,
+        &ctx.data().config.minecraft,
+    )
+    .await
+    {
+        Ok(r) => r,
+        Err(e) => {
+            return respond_error(
+                format!("Something went wrong trying to add {ign} to the whitelist!"),
+                e,
+                &ctx,
+            )
+            .await;
+        }
+    };
+
+    let has_access_everywhere = ctx
+        .data()
+        .config
+        .minecraft
+        .iter()
+        .all(|config| config.allows(trial_member));
+
+    if has_access_everywhere && is_all_success(&results) {
         ctx.say(format!(
             "Successfully added `{ign}` to the whitelist on all servers."
         ))
         .await?;
     } else {
-        let embed = build_whitelist_report_embed(ctx.author(), ign, &results);
+        let embed = build_whitelist_report_embed(
+            ctx.author(),
+            format!("Whitelist Results for {ign}"),
+            &results,
+        );
         ctx.send(CreateReply::default().embed(embed)).await?;
     }
 
@@ -84,7 +123,8 @@ async fn remove(
 
     let results = match add_remove_whitelist(
         ign.as_str(),
-        ctx.data().config.minecraft.clone(),
+        &ctx.data().rcon,
+        &ctx.data().config.minecraft,
         false,
     )
     .await
@@ -106,7 +146,11 @@ async fn remove(
         ))
         .await?;
     } else {
-        let embed = build_whitelist_report_embed(ctx.author(), ign, &results);
+        let embed = build_whitelist_report_embed(
+            ctx.author(),
+            format!("Whitelist Results for {ign}"),
+            &results,
+        );
         ctx.send(CreateReply::default().embed(embed)).await?;
     }
 
@@ -116,11 +160,19 @@ async fn remove(
 #[poise::command(slash_command, guild_only = true)]
 async fn list(
     ctx: AppContext<'_>,
-    #[description = "The server to get the whitelist from."] server: ServerChoice,
+    #[description = "The server to get the whitelist from."]
+    #[autocomplete = "autocomplete_server"]
+    server_choice: String,
 ) -> anyhow::Result<()> {
     ctx.defer().await?;
 
-    let whitelist = match get_whitelist(ctx.data().config.minecraft.get(server)).await {
+    let Some(server) = ctx.data().config.minecraft.get(&server_choice) else {
+        ctx.say(format!("There is no server called {server_choice}!"))
+            .await?;
+        return Ok(());
+    };
+
+    let whitelist = match get_whitelist(&ctx.data().rcon, server).await {
         Ok(whitelist) => whitelist,
         Err(e) => {
             return respond_error(
@@ -142,31 +194,131 @@ async fn list(
     let display = whitelist
         .into_iter()
         .map(escape_markdown)
-        .collect::<Vec<String>>()
-        .join("\n");
+        .collect::<Vec<String>>();
 
     let embed = default_embed(ctx.author())
         .title(format!("{server} Whitelist"))
-        .description(display)
         .field("Count", count.to_string(), false);
 
-    ctx.send(poise::CreateReply::default().embed(embed)).await?;
+    send_paginated(ctx, embed, display, 25, "\n").await?;
 
     Ok(())
 }
 
-async fn get_whitelist(server_config: &ServerConfig) -> anyhow::Result<Vec<String>> {
-    let server_choice = ServerChoice::try_from(server_config)?;
+/// Sync the whitelist on all servers with the member database.
+#[poise::command(slash_command, guild_only = true)]
+async fn sync(ctx: AppContext<'_>) -> anyhow::Result<()> {
+    ctx.defer().await?;
+
+    let whitelist = Whitelist {
+        rcon: &ctx.data().rcon,
+    };
 
-    let mut player_list = run_rcon_command(server_config, vec!["whitelist list"])
-        .await?
+    let diffs = match compute_list_diffs(
+        &ctx.data().db_pool,
+        &ctx.data().config.minecraft,
+        &whitelist,
+    )
+    .await
+    {
+        Ok(diffs) => diffs,
+        Err(e) => {
+            return respond_error("Failed to compute the whitelist changes", e, &ctx).await;
+        }
+    };
+
+    if diffs.iter().all(WhitelistDiff::is_empty) {
+        ctx.say("The whitelists on all servers are already in sync with the member database.")
+            .await?;
+        return Ok(());
+    }
+
+    let embed = build_whitelist_diff_embed(
+        default_embed(ctx.author()).title("Whitelist Sync Preview"),
+        &diffs,
+    );
+
+    let reply = CreateReply::default()
+        .embed(embed)
+        .components(confirm_cancel_component());
+
+    ctx.send(reply).await?;
+
+    if let Some(collector) = ComponentInteractionCollector::new(ctx)
+        .author_id(ctx.author().id)
+        .channel_id(ctx.channel_id())
+        .timeout(Duration::from_secs(120))
+        .await
+        .filter(move |c| {
+            c.data.custom_id.as_str() == "confirm" || c.data.custom_id.as_str() == "cancel"
+        })
+    {
+        if collector.data.custom_id.as_str() == "cancel" {
+            let response = CreateInteractionResponse::UpdateMessage(
+                CreateInteractionResponseMessage::new()
+                    .content("Cancelled the whitelist sync!")
+                    .components(vec![]),
+            );
+
+            collector.create_response(&ctx, response).await?;
+            return Ok(());
+        }
+
+        let response = CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .content("Applying whitelist changes...")
+                .components(vec![]),
+        );
+
+        collector.create_response(&ctx, response).await?;
+
+        let results = apply_list_diffs(
+            &ctx.data().rcon,
+            &ctx.data().config.minecraft,
+            &diffs,
+            &whitelist,
+        )
+        .await;
+
+        let embed = build_whitelist_diff_embed(
+            default_embed(ctx.author()).title("Whitelist Sync Results"),
+            &results,
+        );
+
+        collector
+            .edit_response(
+                &ctx,
+                EditInteractionResponse::new()
+                    .content("Finished applying whitelist changes.")
+                    .embed(embed),
+            )
+            .await?;
+    }
+
+    Ok(())
+}
+
+pub async fn get_whitelist(
+    rcon: &RconManager,
+    server_config: &ServerConfig,
+) -> anyhow::Result<Vec<String>> {
+    let response = run_rcon_command(rcon, server_config, vec!["whitelist list"])
+        .await
         .into_iter()
-        .flatten()
         .next()
-        .context("{server_choice} returned an unexpected or empty response")?
+        .context(format!("{server_config} did not return a response"))??
+        .context(format!(
+            "{server_config} returned an unexpected or empty response"
+        ))?;
+
+    if response.starts_with("There are no whitelisted players") {
+        return Ok(Vec::new());
+    }
+
+    let mut player_list = response
         .split(": ")
         .nth(1)
-        .context("{server_choice} returned an unexpected response")?
+        .context(format!("{server_config} returned an unexpected response"))?
         .split(", ")
         .map(str::to_string)
         .collect::<Vec<String>>();
@@ -176,7 +328,7 @@ async fn get_whitelist(server_config: &ServerConfig) -> anyhow::Result<Vec<Strin
     Ok(player_list)
 }
 
-enum WhitelistResult {
+pub enum WhitelistResult {
     Success,
     Already,
     Fail,
@@ -196,7 +348,7 @@ impl FromStr for WhitelistResult {
     }
 }
 
-enum OpResult {
+pub enum OpResult {
     Success,
     Already,
     Fail,
@@ -216,10 +368,10 @@ impl FromStr for OpResult {
     }
 }
 
-struct WhitelistResultElement {
-    server: ServerChoice,
-    whitelist: WhitelistResult,
-    op: Option<OpResult>,
+pub struct WhitelistResultElement {
+    pub server: String,
+    pub whitelist: Option<WhitelistResult>,
+    pub op: Option<OpResult>,
 }
 
 fn get_whitelist_add_commands(ign: &str, operator: bool) -> Vec<String> {
@@ -241,9 +393,10 @@ fn get_whitelist_remove_commands(ign: &str, operator: bool) -> Vec<String> {
     commands
 }
 
-async fn add_remove_whitelist(
+pub async fn add_remove_whitelist(
     ign: impl Into<String>,
-    configs: MinecraftConfig,
+    rcon: &RconManager,
+    configs: &MinecraftConfig,
     add: bool,
 ) -> anyhow::Result<Vec<WhitelistResultElement>> {
     let ign = ign.into();
@@ -251,19 +404,17 @@ async fn add_remove_whitelist(
     let mut results = Vec::new();
 
     for config in configs {
-        let server_choice = ServerChoice::try_from(&config)?;
-
         let commands = if add {
             get_whitelist_add_commands(ign.as_str(), config.operator)
         } else {
             get_whitelist_remove_commands(ign.as_str(), config.operator)
         };
 
-        let (w, o) = run_whitelist_rcon(&config, commands).await?;
+        let (w, o) = run_whitelist_rcon(rcon, config, commands).await?;
 
         let element = WhitelistResultElement {
-            server: server_choice,
-            whitelist: w,
+            server: config.to_string(),
+            whitelist: Some(w),
             op: o,
         };
 
@@ -273,18 +424,66 @@ async fn add_remove_whitelist(
     Ok(results)
 }
 
+/// Whitelists the player on every server their member status gives them access to and removes them
+/// from all other servers.
+pub async fn apply_access_policy(
+    ign: impl Into<String>,
+    trial_member: bool,
+    rcon: &RconManager,
+    configs: &MinecraftConfig,
+) -> anyhow::Result<Vec<WhitelistResultElement>> {
+    let ign = ign.into();
+
+    let mut results = Vec::new();
+
+    for config in configs {
+        let allowed = config.allows(trial_member);
+
+        let commands = if allowed {
+            get_whitelist_add_commands(ign.as_str(), config.operator)
+        } else {
+            get_whitelist_remove_commands(ign.as_str(), config.operator)
+        };
+
+        let (w, o) = run_whitelist_rcon(rcon, config, commands).await?;
+
+        let server = if allowed {
+            config.to_string()
+        } else {
+            format!("{config} (no access)")
+        };
+
+        results.push(WhitelistResultElement {
+            server,
+            whitelist: Some(w),
+            op: o,
+        });
+    }
+
+    Ok(results)
+}
+
+/// Players that are not in the member database are treated like trial members.
+pub async fn is_trial_ign(db_pool: &PgPool, ign: &str) -> anyhow::Result<bool> {
+    let member = get_member_by_ign(db_pool, ign).await?;
+
+    Ok(member.is_none_or(|member| member.trial_member))
+}
+
+pub async fn get_member_by_ign(
+    db_pool: &PgPool,
+    ign: &str,
+) -> anyhow::Result<Option<MinecraftMember>> {
+    let profile = MojangAPI::get_profile_from_username(ign).await?;
+
+    MemberModelController::get_by_minecraft_uuid(db_pool, &profile.id).await
+}
+
 async fn run_whitelist_rcon(
+    rcon: &RconManager,
     config: &ServerConfig,
     commands: Vec<String>,
 ) -> anyhow::Result<(WhitelistResult, Option<OpResult>)> {
-    let host = config.host.as_str().parse::<Ipv4Addr>()?;
-    let addr = SocketAddr::new(host.into(), config.rcon_port);
-
-    let mut connection = Builder::new()
-        .enable_minecraft_quirks(true)
-        .connect(addr, &config.rcon_password)
-        .await?;
-
     let mut whitelist_result = WhitelistResult::Fail;
     let mut op_result = if commands.len() == 2 {
         Some(OpResult::Fail)
@@ -292,34 +491,30 @@ async fn run_whitelist_rcon(
         None
     };
 
-    for (i, command) in commands.into_iter().enumerate() {
-        let response = connection.cmd(command.as_str()).await;
+    let responses = run_rcon_command(rcon, config, commands).await;
 
-        if let Ok(response) = response {
-            if response.is_empty() {
-                continue;
-            }
+    for (i, response) in responses.into_iter().enumerate() {
+        let Ok(Some(response)) = response else {
+            continue;
+        };
 
-            if i == 0 {
-                whitelist_result = WhitelistResult::from_str(response.as_str())?;
-            } else if i == 1 {
-                op_result = Some(OpResult::from_str(response.as_str())?);
-            } else {
-                anyhow::bail!("Received unexpected response length from server");
-            }
+        if i == 0 {
+            whitelist_result = WhitelistResult::from_str(response.as_str())?;
+        } else if i == 1 {
+            op_result = Some(OpResult::from_str(response.as_str())?);
         } else {
-            continue;
+            anyhow::bail!("Received unexpected response length from server");
         }
     }
 
     Ok((whitelist_result, op_result))
 }
 
-fn is_all_success(elements: &[WhitelistResultElement]) -> bool {
+pub fn is_all_success(elements: &[WhitelistResultElement]) -> bool {
     for element in elements {
         let w: ControlFlow<(), ()> = match element.whitelist {
-            WhitelistResult::Success => ControlFlow::Continue(()),
-            WhitelistResult::Already | WhitelistResult::Fail => ControlFlow::Break(()),
+            Some(WhitelistResult::Success) | None => ControlFlow::Continue(()),
+            Some(WhitelistResult::Already | WhitelistResult::Fail) => ControlFlow::Break(()),
         };
 
         let o: ControlFlow<(), ()> = if let Some(o) = element.op.as_ref() {
@@ -339,33 +534,356 @@ fn is_all_success(elements: &[WhitelistResultElement]) -> bool {
     true
 }
 
-fn build_whitelist_report_embed(
+pub fn build_whitelist_report_embed(
     interaction_user: &User,
-    ign: String,
+    title: String,
     elements: &[WhitelistResultElement],
 ) -> CreateEmbed {
     let fields = elements.iter().map(|element| {
-        let name = element.server.to_string();
-        let mut value = match element.whitelist {
-            WhitelistResult::Success => "**Whitelist**: Success".to_string(),
-            WhitelistResult::Already => "**Whitelist**: Already".to_string(),
-            WhitelistResult::Fail => "**Whitelist**: Failed".to_string(),
-        };
+        let name = element.server.clone();
+        let mut lines = Vec::new();
+
+        if let Some(whitelist) = &element.whitelist {
+            let whitelist = match whitelist {
+                WhitelistResult::Success => "**Whitelist**: Success",
+                WhitelistResult::Already => "**Whitelist**: Already",
+                WhitelistResult::Fail => "**Whitelist**: Failed",
+            };
 
-        if element.op.is_some() {
-            let op = match element.op.as_ref().unwrap() {
-                OpResult::Success => "\n**Operator**: Success",
-                OpResult::Already => "\n**Operator**: Already",
-                OpResult::Fail => "\n**Operator**: Failed",
+            lines.push(whitelist);
+        }
+
+        if let Some(op) = &element.op {
+            let op = match op {
+                OpResult::Success => "**Operator**: Success",
+                OpResult::Already => "**Operator**: Already",
+                OpResult::Fail => "**Operator**: Failed",
             };
 
-            value.push_str(op);
+            lines.push(op);
+        }
+
+        (name, lines.join("\n"), false)
+    });
+
+    default_embed(interaction_user).title(title).fields(fields)
+}
+
+/// How many Mojang profiles are looked up at the same time when resolving member IGNs.
+const MOJANG_CONCURRENCY: usize = 8;
+
+/// The difference between the whitelist on a server and the whitelist derived from the database.
+pub struct WhitelistDiff {
+    pub server_name: String,
+    pub server: String,
+    pub add: Vec<String>,
+    pub remove: Vec<String>,
+    pub failed: Vec<String>,
+    /// Member accounts whose IGN could neither be resolved nor found in the player sessions. They
+    /// are only reported, they do not make the diff non-empty.
+    pub unresolved: Vec<Uuid>,
+}
+
+impl WhitelistDiff {
+    pub fn is_empty(&self) -> bool {
+        self.add.is_empty() && self.remove.is_empty() && self.failed.is_empty()
+    }
+}
+
+pub struct MemberIgn {
+    pub name: String,
+    pub trial_member: bool,
+}
+
+pub struct MemberIgns {
+    pub igns: Vec<MemberIgn>,
+    pub unresolved: Vec<Uuid>,
+}
+
+/// Resolves the IGNs of every account connected to a member in the database. Accounts that fail
+/// to resolve fall back to the name they last played with, and are returned as unresolved if they
+/// never played.
+pub async fn get_member_igns(db_pool: &PgPool) -> anyhow::Result<MemberIgns> {
+    let members = MemberModelController::get_all(db_pool).await?;
+
+    let accounts = members.into_iter().flat_map(|member| {
+        member
+            .minecraft_uuids
+            .into_iter()
+            .map(move |uuid| (uuid, member.trial_member))
+    });
+
+    let profiles = stream::iter(accounts)
+        .map(|(uuid, trial_member)| async move {
+            let profile = MojangAPI::get_profile_from_uuid(&uuid).await;
+            (uuid, trial_member, profile)
+        })
+        .buffer_unordered(MOJANG_CONCURRENCY)
+        .collect::<Vec<_>>()
+        .await;
+
+    let mut member_igns = MemberIgns {
+        igns: Vec::new(),
+        unresolved: Vec::new(),
+    };
+
+    let mut failed = Vec::new();
+
+    for (uuid, trial_member, profile) in profiles {
+        match profile {
+            Ok(profile) => member_igns.igns.push(MemberIgn {
+                name: profile.name,
+                trial_member,
+            }),
+            Err(e) => {
+                tracing::warn!("Failed to resolve the IGN of {uuid}: {e:#}");
+                failed.push((uuid, trial_member));
+            }
+        }
+    }
+
+    if failed.is_empty() {
+        return Ok(member_igns);
+    }
+
+    let uuids = failed.iter().map(|(uuid, _)| *uuid).collect::<Vec<Uuid>>();
+    let sessions = PlayerSessionModelController::get_last_sessions(db_pool, &uuids).await?;
+
+    for (uuid, trial_member) in failed {
+        match sessions.iter().find(|s| s.minecraft_uuid == uuid) {
+            Some(session) => member_igns.igns.push(MemberIgn {
+                name: session.name.clone(),
+                trial_member,
+            }),
+            None => member_igns.unresolved.push(uuid),
+        }
+    }
+
+    Ok(member_igns)
+}
+
+/// A list of players on every server that is kept in sync with the member database.
+pub trait SyncedList {
+    /// Whether a member with the given trial status belongs on the list of the server.
+    fn includes(&self, config: &ServerConfig, trial_member: bool) -> bool;
+
+    fn fetch(
+        &self,
+        config: &ServerConfig,
+    ) -> impl Future<Output = anyhow::Result<Vec<String>>> + Send;
+
+    fn add_command(&self, ign: &str) -> String;
+
+    fn remove_command(&self, ign: &str) -> String;
+
+    /// Whether the rcon response to an add or remove command means the change went through.
+    fn succeeded(&self, response: &str) -> bool;
+}
+
+pub struct Whitelist<'a> {
+    pub rcon: &'a RconManager,
+}
+
+impl SyncedList for Whitelist<'_> {
+    fn includes(&self, config: &ServerConfig, trial_member: bool) -> bool {
+        config.allows(trial_member)
+    }
+
+    async fn fetch(&self, config: &ServerConfig) -> anyhow::Result<Vec<String>> {
+        get_whitelist(self.rcon, config)
+            .await
+            .context(format!("Failed to get the whitelist of {config}"))
+    }
+
+    fn add_command(&self, ign: &str) -> String {
+        format!("whitelist add {ign}")
+    }
+
+    fn remove_command(&self, ign: &str) -> String {
+        format!("whitelist remove {ign}")
+    }
+
+    fn succeeded(&self, response: &str) -> bool {
+        matches!(
+            WhitelistResult::from_str(response),
+            Ok(WhitelistResult::Success | WhitelistResult::Already)
+        )
+    }
+}
+
+/// Compares the list on every server with the members that should be on it.
+pub async fn compute_list_diffs(
+    db_pool: &PgPool,
+    configs: &MinecraftConfig,
+    list: &impl SyncedList,
+) -> anyhow::Result<Vec<WhitelistDiff>> {
+    let members = get_member_igns(db_pool).await?;
+
+    let mut diffs = Vec::new();
+
+    for config in configs {
+        let desired = members
+            .igns
+            .iter()
+            .filter(|member| list.includes(config, member.trial_member))
+            .map(|member| member.name.clone())
+            .collect::<Vec<String>>();
+
+        let desired_lower = desired
+            .iter()
+            .map(|ign| ign.to_lowercase())
+            .collect::<HashSet<String>>();
+
+        let current = list.fetch(config).await?;
+
+        let current_lower = current
+            .iter()
+            .map(|ign| ign.to_lowercase())
+            .collect::<HashSet<String>>();
+
+        let mut add = desired
+            .into_iter()
+            .filter(|ign| !current_lower.contains(&ign.to_lowercase()))
+            .collect::<Vec<String>>();
+
+        let mut remove = current
+            .into_iter()
+            .filter(|ign| !desired_lower.contains(&ign.to_lowercase()))
+            .collect::<Vec<String>>();
+
+        sort_player_list(&mut add);
+        sort_player_list(&mut remove);
+
+        diffs.push(WhitelistDiff {
+            server_name: config.server_name.clone(),
+            server: config.to_string(),
+            add,
+            remove,
+            failed: Vec::new(),
+            unresolved: members.unresolved.clone(),
+        });
+    }
+
+    Ok(diffs)
+}
+
+/// Applies the diffs and returns what was actually changed on every server.
+pub async fn apply_list_diffs(
+    rcon: &RconManager,
+    configs: &MinecraftConfig,
+    diffs: &[WhitelistDiff],
+    list: &impl SyncedList,
+) -> Vec<WhitelistDiff> {
+    let mut results = Vec::new();
+
+    for diff in diffs {
+        let mut result = WhitelistDiff {
+            server_name: diff.server_name.clone(),
+            server: diff.server.clone(),
+            add: Vec::new(),
+            remove: Vec::new(),
+            failed: Vec::new(),
+            unresolved: diff.unresolved.clone(),
+        };
+
+        let Some(config) = configs.get(&diff.server_name) else {
+            result
+                .failed
+                .extend(diff.add.iter().chain(&diff.remove).cloned());
+            results.push(result);
+            continue;
+        };
+
+        let commands = diff
+            .add
+            .iter()
+            .map(|ign| list.add_command(ign))
+            .chain(diff.remove.iter().map(|ign| list.remove_command(ign)))
+            .collect::<Vec<String>>();
+
+        let responses = run_rcon_command(rcon, config, commands).await;
+        let igns = diff
+            .add
+            .iter()
+            .map(|ign| (ign, true))
+            .chain(diff.remove.iter().map(|ign| (ign, false)));
+
+        for ((ign, add), response) in igns.zip(responses) {
+            let success = matches!(response, Ok(Some(response)) if list.succeeded(&response));
+
+            match (success, add) {
+                (true, true) => result.add.push(ign.clone()),
+                (true, false) => result.remove.push(ign.clone()),
+                (false, _) => result.failed.push(ign.clone()),
+            }
         }
 
-        (name, value, false)
+        results.push(result);
+    }
+
+    results
+}
+
+pub fn build_whitelist_diff_embed(embed: CreateEmbed, diffs: &[WhitelistDiff]) -> CreateEmbed {
+    let fields = diffs.iter().map(|diff| {
+        let value = if diff.is_empty() {
+            "In sync".to_string()
+        } else {
+            let mut lines = Vec::new();
+
+            if !diff.add.is_empty() {
+                lines.push(format!("**Add**: {}", display_igns(&diff.add)));
+            }
+
+            if !diff.remove.is_empty() {
+                lines.push(format!("**Remove**: {}", display_igns(&diff.remove)));
+            }
+
+            if !diff.failed.is_empty() {
+                lines.push(format!("**Failed**: {}", display_igns(&diff.failed)));
+            }
+
+            lines.join("\n")
+        };
+
+        (diff.server.clone(), value, false)
     });
 
-    default_embed(interaction_user)
-        .title(format!("Whitelist Results for {}", ign))
-        .fields(fields)
+    let embed = embed.fields(fields);
+
+    // every diff carries the same unresolved accounts, they are listed once
+    match diffs.first().filter(|diff| !diff.unresolved.is_empty()) {
+        Some(diff) => {
+            let uuids = diff
+                .unresolved
+                .iter()
+                .map(|uuid| uuid.to_string())
+                .collect::<Vec<String>>();
+
+            embed.field("Unresolved Accounts", display_igns(&uuids), false)
+        }
+        None => embed,
+    }
+}
+
+/// Joins IGNs so they still fit into an embed field when there are a lot of them.
+pub fn display_igns(igns: &[String]) -> String {
+    let mut display = String::new();
+
+    for (i, ign) in igns.iter().enumerate() {
+        let ign = escape_markdown(ign);
+
+        if display.len() + ign.len() > 300 {
+            display.push_str(&format!("... and {} more", igns.len() - i));
+            break;
+        }
+
+        if !display.is_empty() {
+            display.push_str(", ");
+        }
+
+        display.push_str(&ign);
+    }
+
+    display
 }
@@ -1,52 +1,9 @@
-use std::{
-    fmt::{Debug, Display},
-    num::NonZeroU64,
-};
+use std::{collections::HashSet, fmt::Display, num::NonZeroU64};
 
 use anyhow::Context;
 use poise::serenity_prelude as serenity;
 use serde::Deserialize;
 
-#[derive(Debug, Copy, Clone, poise::ChoiceParameter)]
-#[repr(usize)]
-pub enum ServerChoice {
-    Smp,
-    Cmp,
-    Cmp2,
-    Copy,
-    Snapshots,
-}
-
-impl Display for ServerChoice {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        match self {
-            ServerChoice::Smp => write!(f, "SMP"),
-            ServerChoice::Cmp => write!(f, "CMP"),
-            ServerChoice::Cmp2 => write!(f, "CMP2"),
-            ServerChoice::Copy => write!(f, "Copy"),
-            ServerChoice::Snapshots => write!(f, "Snapshots"),
-        }
-    }
-}
-
-impl TryFrom<&ServerConfig> for ServerChoice {
-    type Error = anyhow::Error;
-
-    fn try_from(server_config: &ServerConfig) -> anyhow::Result<Self> {
-        match server_config.server_name.as_str() {
-            "smp" => Ok(Self::Smp),
-            "cmp" => Ok(Self::Cmp),
-            "cmp2" => Ok(Self::Cmp2),
-            "copy" => Ok(Self::Copy),
-            "snapshots" => Ok(Self::Snapshots),
-            _ => Err(anyhow::anyhow!(
-                "Unknown server choice: {}",
-                server_config.server_name
-            )),
-        }
-    }
-}
-
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
     pub bot: BotConfig,
@@ -56,6 +13,20 @@ pub struct Config {
     pub channels: ChannelConfig,
     pub categories: CategoryConfig,
     pub pterodactyl: PterodactylConfig,
+    #[serde(default)]
+    pub whitelist_sync: Option<WhitelistSyncConfig>,
+    #[serde(default)]
+    pub trial: Option<TrialConfig>,
+    #[serde(default)]
+    pub message_log: MessageLogConfig,
+    #[serde(default)]
+    pub metrics: Option<MetricsConfig>,
+    #[serde(default)]
+    pub lag_watchdog: Option<LagWatchdogConfig>,
+    #[serde(default)]
+    pub status_board: Option<StatusBoardConfig>,
+    #[serde(default)]
+    pub playtime: Option<PlaytimeConfig>,
 }
 
 impl Config {
@@ -63,7 +34,15 @@ impl Config {
         let config_file = std::fs::File::open("config.json")?;
         let reader = std::io::BufReader::new(config_file);
 
-        serde_json::from_reader(reader).context("Failed to parse config.json file")
+        let config: Self =
+            serde_json::from_reader(reader).context("Failed to parse config.json file")?;
+
+        config
+            .minecraft
+            .validate()
+            .context("Invalid minecraft server configuration")?;
+
+        Ok(config)
     }
 }
 
@@ -74,64 +53,107 @@ pub struct BotConfig {
     pub guild_id: serenity::GuildId,
 }
 
+#[derive(Debug, Deserialize, Clone)]
+pub struct WhitelistSyncConfig {
+    pub interval_hours: u64,
+    /// Apply the changes automatically instead of only posting a preview.
+    #[serde(default)]
+    pub apply: bool,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct TrialConfig {
+    /// How long a trial lasts before it is due for review.
+    pub duration_days: u64,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct MessageLogConfig {
+    /// Channels whose edited and deleted messages are not logged.
+    #[serde(default)]
+    pub ignored_channels: Vec<serenity::ChannelId>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MetricsConfig {
+    /// How often the performance of every running server is sampled.
+    pub interval_minutes: u64,
+    /// Samples older than this are deleted. Samples are kept forever when unset.
+    #[serde(default)]
+    pub retention_days: Option<u64>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LagWatchdogConfig {
+    pub interval_seconds: u64,
+    /// The role pinged in the bot log when a server lags.
+    pub role: serenity::RoleId,
+    /// The minimum time between two alerts for the same server.
+    #[serde(default)]
+    pub cooldown_minutes: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct StatusBoardConfig {
+    /// How often the status board in `channels.server_info` is refreshed.
+    pub interval_seconds: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct PlaytimeConfig {
+    /// How often the player lists are checked for joins and leaves.
+    pub interval_seconds: u64,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct DatabaseConfig {
     pub url: String,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Deserialize, Clone)]
+#[serde(transparent)]
 pub struct MinecraftConfig {
-    configs: [ServerConfig; 5],
+    servers: Vec<ServerConfig>,
 }
 
 impl MinecraftConfig {
-    pub fn get(&self, choice: ServerChoice) -> &ServerConfig {
-        &self.configs[choice as usize]
+    pub fn get(&self, server_name: &str) -> Option<&ServerConfig> {
+        self.servers
+            .iter()
+            .find(|s| s.server_name.eq_ignore_ascii_case(server_name.trim()))
     }
 
-    pub fn get_owned(self, choice: ServerChoice) -> ServerConfig {
-        self.configs.into_iter().nth(choice as usize).unwrap()
+    pub fn iter(&self) -> std::slice::Iter<'_, ServerConfig> {
+        self.servers.iter()
     }
-}
 
-macro_rules! declare_config_getters {
-    ($getter_name: ident, $owned_getter_name: ident, $choice: ident) => {
-        impl MinecraftConfig {
-            pub fn $getter_name(&self) -> &ServerConfig {
-                self.get(ServerChoice::$choice)
+    fn validate(&self) -> anyhow::Result<()> {
+        if self.servers.is_empty() {
+            anyhow::bail!("At least one minecraft server has to be configured");
+        }
+
+        let mut seen = HashSet::new();
+
+        for server in &self.servers {
+            if server.server_name.trim().is_empty() {
+                anyhow::bail!("Server names cannot be empty");
             }
 
-            pub fn $owned_getter_name(self) -> ServerConfig {
-                self.get_owned(ServerChoice::$choice)
+            if !seen.insert(server.server_name.to_lowercase()) {
+                anyhow::bail!("Server name {} is configured twice", server.server_name);
             }
         }
-    };
-}
-
-declare_config_getters!(smp, smp_owned, Smp);
-declare_config_getters!(cmp, cmp_owned, Cmp);
-declare_config_getters!(cmp2, cmp2_owned, Cmp2);
-declare_config_getters!(copy, copy_owned, Copy);
-declare_config_getters!(snapshots, snapshots_owned, Snapshots);
-
-impl Debug for MinecraftConfig {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_struct("MinecraftConfig")
-            .field("smp", &self.configs[ServerChoice::Smp as usize])
-            .field("cmp", &self.configs[ServerChoice::Cmp as usize])
-            .field("cmp2", &self.configs[ServerChoice::Cmp2 as usize])
-            .field("copy", &self.configs[ServerChoice::Copy as usize])
-            .field("snapshots", &self.configs[ServerChoice::Snapshots as usize])
-            .finish()
+
+        Ok(())
     }
 }
 
 impl IntoIterator for MinecraftConfig {
     type Item = ServerConfig;
-    type IntoIter = std::array::IntoIter<Self::Item, 5>;
+    type IntoIter = std::vec::IntoIter<Self::Item>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.configs.into_iter()
+        self.servers.into_iter()
     }
 }
 
@@ -140,48 +162,106 @@ impl<'a> IntoIterator for &'a MinecraftConfig {
     type IntoIter = std::slice::Iter<'a, ServerConfig>;
 
     fn into_iter(self) -> Self::IntoIter {
-        self.configs.iter()
-    }
-}
-
-impl<'de> Deserialize<'de> for MinecraftConfig {
-    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-    where
-        D: serde::Deserializer<'de>,
-    {
-        #[derive(Deserialize)]
-        struct DeserializeMinecraftConfig {
-            smp: ServerConfig,
-            cmp: ServerConfig,
-            cmp2: ServerConfig,
-            copy: ServerConfig,
-            snapshots: ServerConfig,
-        }
-
-        let DeserializeMinecraftConfig {
-            smp,
-            cmp,
-            cmp2,
-            copy,
-            snapshots,
-        } = DeserializeMinecraftConfig::deserialize(deserializer)?;
-
-        Ok(MinecraftConfig {
-            configs: [smp, cmp, cmp2, copy, snapshots],
-        })
+        self.servers.iter()
     }
 }
 
 #[derive(Debug, Deserialize, Clone)]
 pub struct ServerConfig {
     pub server_name: String,
+    #[serde(default)]
+    pub display_name: Option<String>,
     pub host: String,
     pub port: u16,
+    #[serde(default)]
+    pub rcon_host: Option<String>,
     pub rcon_port: u16,
     pub rcon_password: String,
     pub panel_id: String,
     pub operator: bool,
     pub backup_limit: u8,
+    /// Restricts `/run` to admins. Defaults to true so servers without the key fail closed.
+    #[serde(default = "default_admin_only")]
+    pub admin_only: bool,
+    #[serde(default)]
+    pub backup_schedule: Option<BackupScheduleConfig>,
+    #[serde(default)]
+    pub power_roles: Vec<serenity::RoleId>,
+    #[serde(default)]
+    pub console_channel: Option<serenity::ChannelId>,
+    #[serde(default)]
+    pub chat_channel: Option<serenity::ChannelId>,
+    #[serde(default)]
+    pub access: ServerAccess,
+    #[serde(default)]
+    pub lag_alert: Option<LagAlertConfig>,
+}
+
+fn default_admin_only() -> bool {
+    true
+}
+
+/// Who gets whitelisted on a server. Players that are not in the member database are treated like
+/// trial members.
+#[derive(Debug, Deserialize, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ServerAccess {
+    #[default]
+    Everyone,
+    MembersOnly,
+}
+
+impl ServerConfig {
+    pub fn rcon_host(&self) -> &str {
+        self.rcon_host.as_deref().unwrap_or(self.host.as_str())
+    }
+
+    pub fn allows(&self, trial_member: bool) -> bool {
+        match self.access {
+            ServerAccess::Everyone => true,
+            ServerAccess::MembersOnly => !trial_member,
+        }
+    }
+}
+
+impl Display for ServerConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.display_name {
+            Some(display_name) => write!(f, "{display_name}"),
+            None => write!(f, "{}", self.server_name),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct LagAlertConfig {
+    pub mspt_threshold: u32,
+    /// How many samples in a row have to be above the threshold before alerting, and below it
+    /// before the server counts as recovered.
+    pub samples: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct BackupScheduleConfig {
+    pub interval_hours: u64,
+    #[serde(default)]
+    pub retention: RetentionConfig,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+pub struct RetentionConfig {
+    #[serde(default)]
+    pub keep_last: usize,
+    #[serde(default)]
+    pub keep_daily: usize,
+    #[serde(default)]
+    pub keep_weekly: usize,
+}
+
+impl RetentionConfig {
+    pub fn is_empty(&self) -> bool {
+        self.keep_last == 0 && self.keep_daily == 0 && self.keep_weekly == 0
+    }
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -0,0 +1,10 @@
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS channel_id VARCHAR(20);
+ALTER TABLE applications ADD COLUMN IF NOT EXISTS voting_message_id VARCHAR(20);
+
+CREATE TABLE IF NOT EXISTS application_votes (
+  application_id INTEGER NOT NULL REFERENCES applications(id) ON DELETE CASCADE,
+  voter_id VARCHAR(20) NOT NULL,
+  vote VARCHAR(10) NOT NULL,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  PRIMARY KEY (application_id, voter_id)
+);
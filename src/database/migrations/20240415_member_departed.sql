@@ -0,0 +1 @@
+ALTER TABLE members ADD COLUMN IF NOT EXISTS departed_at TIMESTAMP;
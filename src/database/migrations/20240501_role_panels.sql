@@ -0,0 +1,18 @@
+CREATE TABLE IF NOT EXISTS role_panels (
+  id SERIAL PRIMARY KEY,
+  title TEXT NOT NULL,
+  description TEXT,
+  style VARCHAR(10) NOT NULL,
+  channel_id VARCHAR(20),
+  message_id VARCHAR(20),
+  created_by VARCHAR(20) NOT NULL,
+  created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE TABLE IF NOT EXISTS role_panel_roles (
+  panel_id INTEGER NOT NULL REFERENCES role_panels(id) ON DELETE CASCADE,
+  role_id VARCHAR(20) NOT NULL,
+  label TEXT NOT NULL,
+  PRIMARY KEY (panel_id, role_id)
+);
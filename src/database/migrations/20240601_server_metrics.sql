@@ -0,0 +1,15 @@
+CREATE TABLE IF NOT EXISTS server_metrics (
+  id BIGSERIAL PRIMARY KEY,
+  server_name VARCHAR(64) NOT NULL,
+  mspt INTEGER NOT NULL,
+  tps INTEGER NOT NULL,
+  player_count INTEGER NOT NULL,
+  max_players INTEGER NOT NULL,
+  mobcap_overworld TEXT NOT NULL,
+  mobcap_the_nether TEXT NOT NULL,
+  mobcap_the_end TEXT NOT NULL,
+  recorded_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS server_metrics_server_recorded_at_idx
+  ON server_metrics (server_name, recorded_at);
@@ -0,0 +1,5 @@
+CREATE TABLE IF NOT EXISTS status_boards (
+  channel_id VARCHAR(20) PRIMARY KEY,
+  message_id VARCHAR(20) NOT NULL,
+  updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
@@ -0,0 +1,11 @@
+CREATE TABLE IF NOT EXISTS player_sessions (
+  id BIGSERIAL PRIMARY KEY,
+  server_name VARCHAR(64) NOT NULL,
+  minecraft_uuid UUID NOT NULL,
+  name VARCHAR(16) NOT NULL,
+  joined_at TIMESTAMP NOT NULL,
+  left_at TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS player_sessions_minecraft_uuid_idx ON player_sessions (minecraft_uuid);
+CREATE INDEX IF NOT EXISTS player_sessions_open_idx ON player_sessions (server_name) WHERE left_at IS NULL;
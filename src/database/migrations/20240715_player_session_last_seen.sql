@@ -0,0 +1,5 @@
+ALTER TABLE player_sessions ADD COLUMN IF NOT EXISTS last_seen_at TIMESTAMP;
+
+UPDATE player_sessions SET last_seen_at = COALESCE(left_at, joined_at) WHERE last_seen_at IS NULL;
+
+ALTER TABLE player_sessions ALTER COLUMN last_seen_at SET NOT NULL;
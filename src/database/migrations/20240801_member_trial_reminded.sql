@@ -0,0 +1 @@
+ALTER TABLE members ADD COLUMN IF NOT EXISTS trial_reminded_at TIMESTAMP;
@@ -0,0 +1,245 @@
+use std::fmt::Display;
+
+use chrono::{DateTime, NaiveDateTime, Utc};
+use poise::serenity_prelude as serenity;
+use serde::{Deserialize, Serialize};
+use serenity::{ChannelId, MessageId, UserId};
+use sqlx::{prelude::FromRow, types::Json, PgPool};
+
+pub struct ApplicationModelController;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationContent {
+    pub igns: Vec<String>,
+    pub age: String,
+    pub timezone: String,
+    pub experience: String,
+    pub about: String,
+}
+
+#[derive(Debug, FromRow)]
+struct DbApplication {
+    id: i32,
+    discord_id: Option<String>,
+    is_open: bool,
+    content: Json<ApplicationContent>,
+    channel_id: Option<String>,
+    voting_message_id: Option<String>,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct Application {
+    pub id: i32,
+    pub discord_id: UserId,
+    pub is_open: bool,
+    pub content: ApplicationContent,
+    pub channel_id: Option<ChannelId>,
+    pub voting_message_id: Option<MessageId>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Vote {
+    Yes,
+    No,
+    Abstain,
+}
+
+#[derive(Debug, Default)]
+pub struct VoteTally {
+    pub yes: i64,
+    pub no: i64,
+    pub abstain: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct DbVoteCount {
+    vote: String,
+    count: i64,
+}
+
+impl TryFrom<DbApplication> for Application {
+    type Error = anyhow::Error;
+
+    fn try_from(db_application: DbApplication) -> Result<Self, Self::Error> {
+        let Some(discord_id) = db_application.discord_id else {
+            anyhow::bail!("Application {} has no applicant", db_application.id)
+        };
+
+        Ok(Application {
+            id: db_application.id,
+            discord_id: UserId::from(discord_id.parse::<u64>()?),
+            is_open: db_application.is_open,
+            content: db_application.content.0,
+            channel_id: db_application
+                .channel_id
+                .map(|id| id.parse::<u64>().map(ChannelId::from))
+                .transpose()?,
+            voting_message_id: db_application
+                .voting_message_id
+                .map(|id| id.parse::<u64>().map(MessageId::from))
+                .transpose()?,
+            created_at: db_application.created_at.and_utc(),
+            updated_at: db_application.updated_at.and_utc(),
+        })
+    }
+}
+
+impl Display for Vote {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Vote::Yes => write!(f, "yes"),
+            Vote::No => write!(f, "no"),
+            Vote::Abstain => write!(f, "abstain"),
+        }
+    }
+}
+
+impl std::str::FromStr for Vote {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> anyhow::Result<Self> {
+        match s {
+            "yes" => Ok(Vote::Yes),
+            "no" => Ok(Vote::No),
+            "abstain" => Ok(Vote::Abstain),
+            _ => anyhow::bail!("\"{s}\" is not a valid vote"),
+        }
+    }
+}
+
+impl ApplicationModelController {
+    pub async fn create(
+        db_pool: &PgPool,
+        user_id: &UserId,
+        content: ApplicationContent,
+    ) -> anyhow::Result<Application> {
+        sqlx::query_as::<_, DbApplication>(
+            r#"
+            INSERT INTO applications
+            (discord_id, is_open, content)
+            VALUES ($1, TRUE, $2)
+            RETURNING *;
+            "#,
+        )
+        .bind(user_id.to_string())
+        .bind(Json(content))
+        .fetch_one(db_pool)
+        .await?
+        .try_into()
+    }
+
+    pub async fn get_by_id(db_pool: &PgPool, id: i32) -> anyhow::Result<Option<Application>> {
+        sqlx::query_as::<_, DbApplication>("SELECT * FROM applications WHERE id = $1;")
+            .bind(id)
+            .fetch_optional(db_pool)
+            .await?
+            .map(Application::try_from)
+            .transpose()
+    }
+
+    pub async fn get_open_by_user(
+        db_pool: &PgPool,
+        user_id: &UserId,
+    ) -> anyhow::Result<Option<Application>> {
+        sqlx::query_as::<_, DbApplication>(
+            "SELECT * FROM applications WHERE discord_id = $1 AND is_open ORDER BY id DESC LIMIT 1;",
+        )
+        .bind(user_id.to_string())
+        .fetch_optional(db_pool)
+        .await?
+        .map(Application::try_from)
+        .transpose()
+    }
+
+    pub async fn set_channels(
+        db_pool: &PgPool,
+        id: i32,
+        channel_id: Option<ChannelId>,
+        voting_message_id: Option<MessageId>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE applications
+            SET channel_id = $1, voting_message_id = $2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $3;
+            "#,
+        )
+        .bind(channel_id.map(|id| id.to_string()))
+        .bind(voting_message_id.map(|id| id.to_string()))
+        .bind(id)
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn close(db_pool: &PgPool, id: i32) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE applications SET is_open = FALSE, updated_at = CURRENT_TIMESTAMP WHERE id = $1;",
+        )
+        .bind(id)
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &PgPool, id: i32) -> anyhow::Result<()> {
+        sqlx::query("DELETE FROM applications WHERE id = $1;")
+            .bind(id)
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Records the vote, replacing any earlier vote of the same voter.
+    pub async fn vote(
+        db_pool: &PgPool,
+        id: i32,
+        voter_id: &UserId,
+        vote: Vote,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO application_votes
+            (application_id, voter_id, vote)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (application_id, voter_id)
+            DO UPDATE SET vote = EXCLUDED.vote, updated_at = CURRENT_TIMESTAMP;
+            "#,
+        )
+        .bind(id)
+        .bind(voter_id.to_string())
+        .bind(vote.to_string())
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn tally(db_pool: &PgPool, id: i32) -> anyhow::Result<VoteTally> {
+        let counts = sqlx::query_as::<_, DbVoteCount>(
+            "SELECT vote, COUNT(*) AS count FROM application_votes WHERE application_id = $1 GROUP BY vote;",
+        )
+        .bind(id)
+        .fetch_all(db_pool)
+        .await?;
+
+        let mut tally = VoteTally::default();
+
+        for count in counts {
+            match count.vote.parse::<Vote>()? {
+                Vote::Yes => tally.yes = count.count,
+                Vote::No => tally.no = count.count,
+                Vote::Abstain => tally.abstain = count.count,
+            }
+        }
+
+        Ok(tally)
+    }
+}
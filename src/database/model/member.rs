@@ -14,6 +14,8 @@ struct DbMinecraftMember {
     minecraft_uuids: Vec<Uuid>,
     member_since: NaiveDateTime,
     updated_at: NaiveDateTime,
+    departed_at: Option<NaiveDateTime>,
+    trial_reminded_at: Option<NaiveDateTime>,
 }
 
 #[derive(Debug, Clone)]
@@ -23,6 +25,11 @@ pub struct MinecraftMember {
     pub minecraft_uuids: Vec<Uuid>,
     pub member_since: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// Set when the member left the discord. The row is kept for history but no longer counts as a
+    /// member.
+    pub departed_at: Option<DateTime<Utc>>,
+    /// When the mod log was last reminded that the trial of this member is due for review.
+    pub trial_reminded_at: Option<DateTime<Utc>>,
 }
 
 #[derive(Debug)]
@@ -51,6 +58,8 @@ impl TryFrom<DbMinecraftMember> for MinecraftMember {
             minecraft_uuids: db_member.minecraft_uuids,
             member_since: db_member.member_since.and_utc(),
             updated_at: db_member.updated_at.and_utc(),
+            departed_at: db_member.departed_at.map(|d| d.and_utc()),
+            trial_reminded_at: db_member.trial_reminded_at.map(|r| r.and_utc()),
         })
     }
 }
@@ -73,8 +82,22 @@ impl MemberModelController {
         }
     }
 
+    pub async fn get_by_minecraft_uuid(
+        db_pool: &PgPool,
+        uuid: &Uuid,
+    ) -> anyhow::Result<Option<MinecraftMember>> {
+        let maybe_db_member = sqlx::query_as::<_, DbMinecraftMember>(
+            "SELECT * FROM members WHERE $1 = ANY(minecraft_uuids) AND departed_at IS NULL;",
+        )
+        .bind(uuid)
+        .fetch_optional(db_pool)
+        .await?;
+
+        maybe_db_member.map(MinecraftMember::try_from).transpose()
+    }
+
     pub async fn get_all(db_pool: &PgPool) -> anyhow::Result<Vec<MinecraftMember>> {
-        sqlx::query_as::<_, DbMinecraftMember>("SELECT * FROM members;")
+        sqlx::query_as::<_, DbMinecraftMember>("SELECT * FROM members WHERE departed_at IS NULL;")
             .fetch_all(db_pool)
             .await?
             .into_iter()
@@ -91,6 +114,13 @@ impl MemberModelController {
             INSERT INTO members
             (discord_id, trial_member, minecraft_uuids, member_since)
             VALUES ($1, $2, $3, $4)
+            ON CONFLICT (discord_id) DO UPDATE SET
+                trial_member = EXCLUDED.trial_member,
+                minecraft_uuids = EXCLUDED.minecraft_uuids,
+                member_since = EXCLUDED.member_since,
+                updated_at = CURRENT_TIMESTAMP,
+                departed_at = NULL
+            WHERE members.departed_at IS NOT NULL
             RETURNING *;
             "#,
         )
@@ -103,6 +133,8 @@ impl MemberModelController {
 
         match query_result {
             Ok(member) => member.try_into(),
+            // the insert only falls back to an update for departed members
+            Err(sqlx::Error::RowNotFound) => anyhow::bail!("Unique constraint violation"),
             Err(e) => {
                 let Some(db_error) = e.as_database_error() else {
                     return Err(anyhow::Error::from(e));
@@ -135,7 +167,7 @@ impl MemberModelController {
             .unwrap_or(current_member.minecraft_uuids);
         let updated_member_since = update_member
             .member_since
-            .unwrap_or(current_member.updated_at.naive_utc());
+            .unwrap_or(current_member.member_since.naive_utc());
         let updated_at = Timestamp::now().naive_local();
 
         sqlx::query_as::<_, DbMinecraftMember>(
@@ -145,7 +177,7 @@ impl MemberModelController {
                 trial_member = $1,
                 minecraft_uuids = $2,
                 member_since = $3,
-                update_at = $4,
+                updated_at = $4
             WHERE discord_id = $5
             RETURNING *;
             "#,
@@ -154,11 +186,41 @@ impl MemberModelController {
         .bind(updated_uuids)
         .bind(updated_member_since)
         .bind(updated_at)
+        .bind(update_member.discord_id.to_string())
         .fetch_one(db_pool)
         .await?
         .try_into()
     }
 
+    pub async fn mark_departed(db_pool: &PgPool, user_id: &UserId) -> anyhow::Result<()> {
+        sqlx::query(
+            "UPDATE members SET departed_at = CURRENT_TIMESTAMP, updated_at = CURRENT_TIMESTAMP WHERE discord_id = $1;",
+        )
+        .bind(user_id.to_string())
+        .execute(db_pool)
+        .await
+        .context(format!("Failed to mark member with id {} as departed", user_id))?;
+
+        Ok(())
+    }
+
+    pub async fn mark_trial_reminded(db_pool: &PgPool, user_ids: &[UserId]) -> anyhow::Result<()> {
+        let ids = user_ids
+            .iter()
+            .map(|id| id.to_string())
+            .collect::<Vec<String>>();
+
+        sqlx::query(
+            "UPDATE members SET trial_reminded_at = CURRENT_TIMESTAMP WHERE discord_id = ANY($1);",
+        )
+        .bind(ids)
+        .execute(db_pool)
+        .await
+        .context("Failed to mark trial reminders as sent")?;
+
+        Ok(())
+    }
+
     pub async fn delete(db_pool: &PgPool, user_id: &UserId) -> anyhow::Result<()> {
         sqlx::query("DELETE FROM members WHERE discord_id = $1;")
             .bind(user_id.to_string())
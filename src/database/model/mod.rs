@@ -1,2 +1,7 @@
+pub mod application;
 pub mod member;
+pub mod player_session;
+pub mod role_panel;
+pub mod server_metrics;
+pub mod status_board;
 pub mod todo;
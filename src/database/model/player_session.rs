@@ -0,0 +1,261 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{prelude::FromRow, PgPool};
+use uuid::Uuid;
+
+pub struct PlayerSessionModelController;
+
+#[derive(Debug, FromRow)]
+struct DbPlayerSession {
+    id: i64,
+    server_name: String,
+    minecraft_uuid: Uuid,
+    name: String,
+    joined_at: NaiveDateTime,
+    left_at: Option<NaiveDateTime>,
+    last_seen_at: NaiveDateTime,
+}
+
+#[derive(Debug, FromRow)]
+struct DbPlaytime {
+    server_name: String,
+    seconds: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct DbLeaderboardEntry {
+    minecraft_uuid: Uuid,
+    name: String,
+    seconds: i64,
+}
+
+#[derive(Debug, FromRow)]
+struct DbName {
+    minecraft_uuid: Uuid,
+    name: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct PlayerSession {
+    pub id: i64,
+    pub server_name: String,
+    pub minecraft_uuid: Uuid,
+    pub name: String,
+    pub joined_at: DateTime<Utc>,
+    pub left_at: Option<DateTime<Utc>>,
+    pub last_seen_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ServerPlaytime {
+    pub server_name: String,
+    pub playtime: chrono::Duration,
+}
+
+#[derive(Debug, Clone)]
+pub struct LeaderboardEntry {
+    pub minecraft_uuid: Uuid,
+    pub name: String,
+    pub playtime: chrono::Duration,
+}
+
+impl From<DbPlayerSession> for PlayerSession {
+    fn from(db_session: DbPlayerSession) -> Self {
+        PlayerSession {
+            id: db_session.id,
+            server_name: db_session.server_name,
+            minecraft_uuid: db_session.minecraft_uuid,
+            name: db_session.name,
+            joined_at: db_session.joined_at.and_utc(),
+            left_at: db_session.left_at.map(|left_at| left_at.and_utc()),
+            last_seen_at: db_session.last_seen_at.and_utc(),
+        }
+    }
+}
+
+impl PlayerSessionModelController {
+    pub async fn get_open(
+        db_pool: &PgPool,
+        server_name: &str,
+    ) -> anyhow::Result<Vec<PlayerSession>> {
+        let sessions = sqlx::query_as::<_, DbPlayerSession>(
+            "SELECT * FROM player_sessions WHERE server_name = $1 AND left_at IS NULL;",
+        )
+        .bind(server_name)
+        .fetch_all(db_pool)
+        .await?
+        .into_iter()
+        .map(PlayerSession::from)
+        .collect();
+
+        Ok(sessions)
+    }
+
+    pub async fn start(
+        db_pool: &PgPool,
+        server_name: &str,
+        minecraft_uuid: &Uuid,
+        name: &str,
+        joined_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO player_sessions
+            (server_name, minecraft_uuid, name, joined_at, last_seen_at)
+            VALUES ($1, $2, $3, $4, $4);
+            "#,
+        )
+        .bind(server_name)
+        .bind(minecraft_uuid)
+        .bind(name)
+        .bind(joined_at.naive_utc())
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn touch(
+        db_pool: &PgPool,
+        ids: &[i64],
+        last_seen_at: DateTime<Utc>,
+    ) -> anyhow::Result<()> {
+        sqlx::query("UPDATE player_sessions SET last_seen_at = $1 WHERE id = ANY($2);")
+            .bind(last_seen_at.naive_utc())
+            .bind(ids)
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Ends the sessions at the last time their player was seen online.
+    pub async fn end(db_pool: &PgPool, ids: &[i64]) -> anyhow::Result<()> {
+        sqlx::query("UPDATE player_sessions SET left_at = last_seen_at WHERE id = ANY($1);")
+            .bind(ids)
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    /// Ends every open session at the last time its player was seen online, returning how many
+    /// were closed.
+    pub async fn end_all_open(db_pool: &PgPool) -> anyhow::Result<u64> {
+        let result =
+            sqlx::query("UPDATE player_sessions SET left_at = last_seen_at WHERE left_at IS NULL;")
+                .execute(db_pool)
+                .await?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Returns the last session of each of the players, even if it is still open.
+    pub async fn get_last_sessions(
+        db_pool: &PgPool,
+        minecraft_uuids: &[Uuid],
+    ) -> anyhow::Result<Vec<PlayerSession>> {
+        let sessions = sqlx::query_as::<_, DbPlayerSession>(
+            r#"
+            SELECT DISTINCT ON (minecraft_uuid) * FROM player_sessions
+            WHERE minecraft_uuid = ANY($1)
+            ORDER BY minecraft_uuid, COALESCE(left_at, 'infinity'::TIMESTAMP) DESC;
+            "#,
+        )
+        .bind(minecraft_uuids)
+        .fetch_all(db_pool)
+        .await?
+        .into_iter()
+        .map(PlayerSession::from)
+        .collect();
+
+        Ok(sessions)
+    }
+
+    /// Finds the players that last played under the given name, ignoring case.
+    pub async fn find_by_name(db_pool: &PgPool, name: &str) -> anyhow::Result<Vec<Uuid>> {
+        let names = sqlx::query_as::<_, DbName>(
+            r#"
+            SELECT DISTINCT ON (minecraft_uuid) minecraft_uuid, name FROM player_sessions
+            WHERE minecraft_uuid IN (SELECT minecraft_uuid FROM player_sessions WHERE LOWER(name) = LOWER($1))
+            ORDER BY minecraft_uuid, joined_at DESC;
+            "#,
+        )
+        .bind(name)
+        .fetch_all(db_pool)
+        .await?;
+
+        Ok(names
+            .into_iter()
+            .filter(|n| n.name.eq_ignore_ascii_case(name))
+            .map(|n| n.minecraft_uuid)
+            .collect())
+    }
+
+    /// Sums up the playtime of the players per server since the given time. Open sessions count
+    /// until their player was last seen.
+    pub async fn playtime(
+        db_pool: &PgPool,
+        minecraft_uuids: &[Uuid],
+        since: Option<DateTime<Utc>>,
+    ) -> anyhow::Result<Vec<ServerPlaytime>> {
+        let playtimes = sqlx::query_as::<_, DbPlaytime>(
+            r#"
+            SELECT
+              server_name,
+              SUM(EXTRACT(EPOCH FROM (COALESCE(left_at, last_seen_at) - GREATEST(joined_at, $2))))::BIGINT AS seconds
+            FROM player_sessions
+            WHERE minecraft_uuid = ANY($1) AND COALESCE(left_at, last_seen_at) > $2
+            GROUP BY server_name
+            ORDER BY seconds DESC;
+            "#,
+        )
+        .bind(minecraft_uuids)
+        .bind(since.unwrap_or(DateTime::UNIX_EPOCH).naive_utc())
+        .fetch_all(db_pool)
+        .await?
+        .into_iter()
+        .map(|p| ServerPlaytime {
+            server_name: p.server_name,
+            playtime: chrono::Duration::seconds(p.seconds),
+        })
+        .collect();
+
+        Ok(playtimes)
+    }
+
+    /// The players with the most playtime since the given time, on one or all servers.
+    pub async fn leaderboard(
+        db_pool: &PgPool,
+        server_name: Option<&str>,
+        since: Option<DateTime<Utc>>,
+        limit: i64,
+    ) -> anyhow::Result<Vec<LeaderboardEntry>> {
+        let entries = sqlx::query_as::<_, DbLeaderboardEntry>(
+            r#"
+            SELECT
+              minecraft_uuid,
+              (ARRAY_AGG(name ORDER BY joined_at DESC))[1] AS name,
+              SUM(EXTRACT(EPOCH FROM (COALESCE(left_at, last_seen_at) - GREATEST(joined_at, $2))))::BIGINT AS seconds
+            FROM player_sessions
+            WHERE ($1::VARCHAR IS NULL OR server_name = $1) AND COALESCE(left_at, last_seen_at) > $2
+            GROUP BY minecraft_uuid
+            ORDER BY seconds DESC
+            LIMIT $3;
+            "#,
+        )
+        .bind(server_name)
+        .bind(since.unwrap_or(DateTime::UNIX_EPOCH).naive_utc())
+        .bind(limit)
+        .fetch_all(db_pool)
+        .await?
+        .into_iter()
+        .map(|e| LeaderboardEntry {
+            minecraft_uuid: e.minecraft_uuid,
+            name: e.name,
+            playtime: chrono::Duration::seconds(e.seconds),
+        })
+        .collect();
+
+        Ok(entries)
+    }
+}
@@ -0,0 +1,208 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, MessageId, RoleId, UserId};
+use sqlx::{prelude::FromRow, PgPool};
+
+use crate::commands::rolepanel::PanelStyle;
+
+pub struct RolePanelModelController;
+
+#[derive(Debug, FromRow)]
+struct DbRolePanel {
+    id: i32,
+    title: String,
+    description: Option<String>,
+    style: String,
+    channel_id: Option<String>,
+    message_id: Option<String>,
+    created_by: String,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+}
+
+#[derive(Debug, FromRow)]
+struct DbRolePanelRole {
+    role_id: String,
+    label: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RolePanel {
+    pub id: i32,
+    pub title: String,
+    pub description: Option<String>,
+    pub style: PanelStyle,
+    pub channel_id: Option<ChannelId>,
+    pub message_id: Option<MessageId>,
+    pub created_by: UserId,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct RolePanelRole {
+    pub role_id: RoleId,
+    pub label: String,
+}
+
+#[derive(Debug)]
+pub struct CreateRolePanel {
+    pub title: String,
+    pub description: Option<String>,
+    pub style: PanelStyle,
+    pub created_by: UserId,
+}
+
+impl TryFrom<DbRolePanel> for RolePanel {
+    type Error = anyhow::Error;
+
+    fn try_from(db_panel: DbRolePanel) -> Result<Self, Self::Error> {
+        Ok(RolePanel {
+            id: db_panel.id,
+            title: db_panel.title,
+            description: db_panel.description,
+            style: db_panel.style.parse()?,
+            channel_id: db_panel
+                .channel_id
+                .map(|id| id.parse::<u64>().map(ChannelId::from))
+                .transpose()?,
+            message_id: db_panel
+                .message_id
+                .map(|id| id.parse::<u64>().map(MessageId::from))
+                .transpose()?,
+            created_by: UserId::from(db_panel.created_by.parse::<u64>()?),
+            created_at: db_panel.created_at.and_utc(),
+            updated_at: db_panel.updated_at.and_utc(),
+        })
+    }
+}
+
+impl TryFrom<DbRolePanelRole> for RolePanelRole {
+    type Error = anyhow::Error;
+
+    fn try_from(db_role: DbRolePanelRole) -> Result<Self, Self::Error> {
+        Ok(RolePanelRole {
+            role_id: RoleId::from(db_role.role_id.parse::<u64>()?),
+            label: db_role.label,
+        })
+    }
+}
+
+impl RolePanelModelController {
+    pub async fn create(db_pool: &PgPool, panel: CreateRolePanel) -> anyhow::Result<RolePanel> {
+        sqlx::query_as::<_, DbRolePanel>(
+            r#"
+            INSERT INTO role_panels
+            (title, description, style, created_by)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *;
+            "#,
+        )
+        .bind(panel.title)
+        .bind(panel.description)
+        .bind(panel.style.to_string())
+        .bind(panel.created_by.to_string())
+        .fetch_one(db_pool)
+        .await?
+        .try_into()
+    }
+
+    pub async fn get_by_id(db_pool: &PgPool, id: i32) -> anyhow::Result<Option<RolePanel>> {
+        sqlx::query_as::<_, DbRolePanel>("SELECT * FROM role_panels WHERE id = $1;")
+            .bind(id)
+            .fetch_optional(db_pool)
+            .await?
+            .map(RolePanel::try_from)
+            .transpose()
+    }
+
+    pub async fn get_all(db_pool: &PgPool) -> anyhow::Result<Vec<RolePanel>> {
+        sqlx::query_as::<_, DbRolePanel>("SELECT * FROM role_panels ORDER BY id;")
+            .fetch_all(db_pool)
+            .await?
+            .into_iter()
+            .map(RolePanel::try_from)
+            .collect()
+    }
+
+    pub async fn set_message(
+        db_pool: &PgPool,
+        id: i32,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            UPDATE role_panels
+            SET channel_id = $1, message_id = $2, updated_at = CURRENT_TIMESTAMP
+            WHERE id = $3;
+            "#,
+        )
+        .bind(channel_id.to_string())
+        .bind(message_id.to_string())
+        .bind(id)
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn delete(db_pool: &PgPool, id: i32) -> anyhow::Result<()> {
+        sqlx::query("DELETE FROM role_panels WHERE id = $1;")
+            .bind(id)
+            .execute(db_pool)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn roles(db_pool: &PgPool, panel_id: i32) -> anyhow::Result<Vec<RolePanelRole>> {
+        sqlx::query_as::<_, DbRolePanelRole>(
+            "SELECT role_id, label FROM role_panel_roles WHERE panel_id = $1 ORDER BY label;",
+        )
+        .bind(panel_id)
+        .fetch_all(db_pool)
+        .await?
+        .into_iter()
+        .map(RolePanelRole::try_from)
+        .collect()
+    }
+
+    /// Adds the role to the panel or updates its label when it is already on the panel.
+    pub async fn add_role(
+        db_pool: &PgPool,
+        panel_id: i32,
+        role: RolePanelRole,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO role_panel_roles
+            (panel_id, role_id, label)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (panel_id, role_id) DO UPDATE SET label = EXCLUDED.label;
+            "#,
+        )
+        .bind(panel_id)
+        .bind(role.role_id.to_string())
+        .bind(role.label)
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn remove_role(
+        db_pool: &PgPool,
+        panel_id: i32,
+        role_id: RoleId,
+    ) -> anyhow::Result<bool> {
+        let result =
+            sqlx::query("DELETE FROM role_panel_roles WHERE panel_id = $1 AND role_id = $2;")
+                .bind(panel_id)
+                .bind(role_id.to_string())
+                .execute(db_pool)
+                .await?;
+
+        Ok(result.rows_affected() > 0)
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sqlx::{prelude::FromRow, PgPool};
+
+use crate::commands::status::ServerMetrics;
+
+pub struct ServerMetricsModelController;
+
+#[derive(Debug, FromRow)]
+struct DbMetricsSummary {
+    samples: i64,
+    min_mspt: Option<i32>,
+    avg_mspt: Option<f64>,
+    max_mspt: Option<i32>,
+    avg_tps: Option<f64>,
+    peak_players: Option<i32>,
+}
+
+#[derive(Debug, FromRow)]
+struct DbMetricsSample {
+    mspt: i32,
+    tps: i32,
+    player_count: i32,
+    max_players: i32,
+    recorded_at: NaiveDateTime,
+}
+
+#[derive(Debug, FromRow)]
+struct DbPeak {
+    recorded_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricsSample {
+    pub mspt: i32,
+    pub tps: i32,
+    pub player_count: i32,
+    pub max_players: i32,
+    pub recorded_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone)]
+pub struct MetricsSummary {
+    pub samples: i64,
+    pub min_mspt: i32,
+    pub avg_mspt: f64,
+    pub max_mspt: i32,
+    pub avg_tps: f64,
+    pub peak_players: i32,
+    pub peak_at: DateTime<Utc>,
+}
+
+impl From<DbMetricsSample> for MetricsSample {
+    fn from(db_sample: DbMetricsSample) -> Self {
+        MetricsSample {
+            mspt: db_sample.mspt,
+            tps: db_sample.tps,
+            player_count: db_sample.player_count,
+            max_players: db_sample.max_players,
+            recorded_at: db_sample.recorded_at.and_utc(),
+        }
+    }
+}
+
+impl ServerMetricsModelController {
+    pub async fn create(
+        db_pool: &PgPool,
+        server_name: &str,
+        metrics: &ServerMetrics,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO server_metrics
+            (server_name, mspt, tps, player_count, max_players, mobcap_overworld, mobcap_the_nether, mobcap_the_end)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8);
+            "#,
+        )
+        .bind(server_name)
+        .bind(i32::try_from(metrics.performance.mspt)?)
+        .bind(i32::try_from(metrics.performance.tps)?)
+        .bind(i32::from(metrics.players.count))
+        .bind(i32::from(metrics.players.max))
+        .bind(&metrics.mobcap.overworld)
+        .bind(&metrics.mobcap.the_nether)
+        .bind(&metrics.mobcap.the_end)
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Summarizes the samples of a server recorded since the given time, `None` if there are none.
+    pub async fn summary(
+        db_pool: &PgPool,
+        server_name: &str,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Option<MetricsSummary>> {
+        let summary = sqlx::query_as::<_, DbMetricsSummary>(
+            r#"
+            SELECT
+              COUNT(*) AS samples,
+              MIN(mspt) AS min_mspt,
+              AVG(mspt)::DOUBLE PRECISION AS avg_mspt,
+              MAX(mspt) AS max_mspt,
+              AVG(tps)::DOUBLE PRECISION AS avg_tps,
+              MAX(player_count) AS peak_players
+            FROM server_metrics
+            WHERE server_name = $1 AND recorded_at >= $2;
+            "#,
+        )
+        .bind(server_name)
+        .bind(since.naive_utc())
+        .fetch_one(db_pool)
+        .await?;
+
+        let (Some(min_mspt), Some(avg_mspt), Some(max_mspt), Some(avg_tps), Some(peak_players)) = (
+            summary.min_mspt,
+            summary.avg_mspt,
+            summary.max_mspt,
+            summary.avg_tps,
+            summary.peak_players,
+        ) else {
+            return Ok(None);
+        };
+
+        let peak = sqlx::query_as::<_, DbPeak>(
+            r#"
+            SELECT recorded_at FROM server_metrics
+            WHERE server_name = $1 AND recorded_at >= $2
+            ORDER BY player_count DESC, recorded_at DESC
+            LIMIT 1;
+            "#,
+        )
+        .bind(server_name)
+        .bind(since.naive_utc())
+        .fetch_one(db_pool)
+        .await?;
+
+        Ok(Some(MetricsSummary {
+            samples: summary.samples,
+            min_mspt,
+            avg_mspt,
+            max_mspt,
+            avg_tps,
+            peak_players,
+            peak_at: peak.recorded_at.and_utc(),
+        }))
+    }
+
+    /// Returns the samples of a server recorded since the given time, oldest first.
+    pub async fn get_samples(
+        db_pool: &PgPool,
+        server_name: &str,
+        since: DateTime<Utc>,
+    ) -> anyhow::Result<Vec<MetricsSample>> {
+        let samples = sqlx::query_as::<_, DbMetricsSample>(
+            r#"
+            SELECT mspt, tps, player_count, max_players, recorded_at FROM server_metrics
+            WHERE server_name = $1 AND recorded_at >= $2
+            ORDER BY recorded_at;
+            "#,
+        )
+        .bind(server_name)
+        .bind(since.naive_utc())
+        .fetch_all(db_pool)
+        .await?
+        .into_iter()
+        .map(MetricsSample::from)
+        .collect();
+
+        Ok(samples)
+    }
+
+    /// Deletes all samples recorded before the given time and returns how many were deleted.
+    pub async fn prune(db_pool: &PgPool, before: DateTime<Utc>) -> anyhow::Result<u64> {
+        let result = sqlx::query("DELETE FROM server_metrics WHERE recorded_at < $1;")
+            .bind(before.naive_utc())
+            .execute(db_pool)
+            .await?;
+
+        Ok(result.rows_affected())
+    }
+}
@@ -0,0 +1,48 @@
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, MessageId};
+use sqlx::{prelude::FromRow, PgPool};
+
+pub struct StatusBoardModelController;
+
+#[derive(Debug, FromRow)]
+struct DbStatusBoard {
+    message_id: String,
+}
+
+impl StatusBoardModelController {
+    pub async fn get_message(
+        db_pool: &PgPool,
+        channel_id: ChannelId,
+    ) -> anyhow::Result<Option<MessageId>> {
+        sqlx::query_as::<_, DbStatusBoard>(
+            "SELECT message_id FROM status_boards WHERE channel_id = $1;",
+        )
+        .bind(channel_id.to_string())
+        .fetch_optional(db_pool)
+        .await?
+        .map(|board| Ok(MessageId::from(board.message_id.parse::<u64>()?)))
+        .transpose()
+    }
+
+    pub async fn set_message(
+        db_pool: &PgPool,
+        channel_id: ChannelId,
+        message_id: MessageId,
+    ) -> anyhow::Result<()> {
+        sqlx::query(
+            r#"
+            INSERT INTO status_boards
+            (channel_id, message_id)
+            VALUES ($1, $2)
+            ON CONFLICT (channel_id)
+            DO UPDATE SET message_id = EXCLUDED.message_id, updated_at = CURRENT_TIMESTAMP;
+            "#,
+        )
+        .bind(channel_id.to_string())
+        .bind(message_id.to_string())
+        .execute(db_pool)
+        .await?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,353 @@
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateMessage, GuildMemberUpdateEvent, Member, RoleId, User};
+
+use crate::{
+    commands::whitelist::{add_remove_whitelist, build_whitelist_report_embed},
+    database::model::member::MemberModelController,
+    util::{
+        builder::default_embed,
+        format::{display, display_time, escape_markdown, fdisplay},
+        mojang::MojangAPI,
+    },
+    Data,
+};
+
+pub async fn handle_member_addition(
+    new_member: &Member,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if new_member.guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    let user = &new_member.user;
+
+    let embed = default_embed(user)
+        .title("Member Joined")
+        .thumbnail(user.face())
+        .field("User", fdisplay(user), false)
+        .field(
+            "Account Created",
+            display_time(user.created_at().to_utc()),
+            false,
+        )
+        .field(
+            "Database Member",
+            display_database_member(data, user).await,
+            false,
+        );
+
+    send_member_log(ctx, data, embed).await
+}
+
+pub async fn handle_member_removal(
+    guild_id: &serenity::GuildId,
+    user: &User,
+    member_data_if_available: &Option<Member>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if *guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    let embed = default_embed(user)
+        .title("Member Left")
+        .thumbnail(user.face())
+        .field("User", fdisplay(user), false);
+
+    let embed = match member_data_if_available {
+        Some(member) => {
+            let embed = match member.joined_at {
+                Some(joined_at) => embed.field("Joined", display_time(joined_at.to_utc()), false),
+                None => embed,
+            };
+
+            embed.field("Roles", display_roles(&member.roles), false)
+        }
+        None => embed,
+    };
+
+    let embed = embed.field(
+        "Database Member",
+        display_database_member(data, user).await,
+        false,
+    );
+
+    send_member_log(ctx, data, embed).await
+}
+
+/// Removes a member that left the discord from all whitelists and marks them as departed. The
+/// database row is kept so their history survives.
+pub async fn offboard_member(
+    guild_id: &serenity::GuildId,
+    user: &User,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if *guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    let Some(member) = MemberModelController::get_by_id(&data.db_pool, &user.id).await? else {
+        return Ok(());
+    };
+
+    if member.departed_at.is_some() {
+        return Ok(());
+    }
+
+    let mut embeds = Vec::new();
+    let mut failures = Vec::new();
+
+    for uuid in &member.minecraft_uuids {
+        let name = match MojangAPI::get_profile_from_uuid(uuid).await {
+            Ok(profile) => profile.name,
+            Err(e) => {
+                tracing::error!("Failed to resolve the IGN of {uuid}: {e}");
+                failures.push(format!("Failed to resolve the IGN of `{uuid}`"));
+                continue;
+            }
+        };
+
+        match add_remove_whitelist(name.as_str(), &data.rcon, &data.config.minecraft, false).await {
+            Ok(results) => embeds.push(build_whitelist_report_embed(
+                user,
+                format!("Whitelist Results for {name}"),
+                &results,
+            )),
+            Err(e) => {
+                tracing::error!("Failed to remove {name} from the whitelist: {e}");
+                failures.push(format!(
+                    "Failed to remove {} from the whitelist",
+                    escape_markdown(name)
+                ));
+            }
+        }
+    }
+
+    let departed = match MemberModelController::mark_departed(&data.db_pool, &user.id).await {
+        Ok(_) => "Yes".to_string(),
+        Err(e) => {
+            tracing::error!("{e:#}");
+            "Failed to mark the member as departed".to_string()
+        }
+    };
+
+    let embed = default_embed(user)
+        .title("Member Offboarded")
+        .thumbnail(user.face())
+        .field("User", fdisplay(user), false)
+        .field("Member Since", display_time(member.member_since), false)
+        .field(
+            "Trial Member",
+            if member.trial_member { "Yes" } else { "No" },
+            false,
+        )
+        .field("Marked As Departed", departed, false);
+
+    let embed = if failures.is_empty() {
+        embed
+    } else {
+        embed
+            .colour(13_382_451)
+            .field("Errors", failures.join("\n"), false)
+    };
+
+    // a message can hold at most 10 embeds
+    let message = std::iter::once(embed)
+        .chain(embeds)
+        .take(10)
+        .fold(CreateMessage::new(), |message, embed| message.embed(embed));
+
+    data.config
+        .channels
+        .mod_log
+        .send_message(ctx, message)
+        .await?;
+
+    Ok(())
+}
+
+/// Logs nickname and role changes. Without the previous state in the cache there is nothing to
+/// compare against, so those updates are skipped.
+pub async fn handle_member_update(
+    old_if_available: &Option<Member>,
+    event: &GuildMemberUpdateEvent,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if event.guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    let Some(old) = old_if_available else {
+        return Ok(());
+    };
+
+    let mut changes = Vec::new();
+
+    if old.nick != event.nick {
+        changes.push((
+            "Nickname",
+            format!(
+                "{} → {}",
+                display_nick(old.nick.as_deref()),
+                display_nick(event.nick.as_deref())
+            ),
+        ));
+    }
+
+    let added = event
+        .roles
+        .iter()
+        .filter(|role| !old.roles.contains(role))
+        .copied()
+        .collect::<Vec<RoleId>>();
+
+    let removed = old
+        .roles
+        .iter()
+        .filter(|role| !event.roles.contains(role))
+        .copied()
+        .collect::<Vec<RoleId>>();
+
+    if !added.is_empty() {
+        changes.push(("Roles Added", display_roles(&added)));
+    }
+
+    if !removed.is_empty() {
+        changes.push(("Roles Removed", display_roles(&removed)));
+    }
+
+    if changes.is_empty() {
+        return Ok(());
+    }
+
+    let user = &event.user;
+
+    let embed = default_embed(user)
+        .title("Member Updated")
+        .thumbnail(user.face())
+        .field("User", fdisplay(user), false)
+        .fields(
+            changes
+                .into_iter()
+                .map(|(name, value)| (name, value, false)),
+        )
+        .field(
+            "Database Member",
+            display_database_member(data, user).await,
+            false,
+        );
+
+    send_member_log(ctx, data, embed).await
+}
+
+pub async fn handle_ban_addition(
+    guild_id: &serenity::GuildId,
+    banned_user: &User,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if *guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    let embed = default_embed(banned_user)
+        .title("Member Banned")
+        .colour(13_382_451)
+        .thumbnail(banned_user.face())
+        .field("User", fdisplay(banned_user), false)
+        .field(
+            "Database Member",
+            display_database_member(data, banned_user).await,
+            false,
+        );
+
+    send_mod_log(ctx, data, embed).await
+}
+
+pub async fn handle_ban_removal(
+    guild_id: &serenity::GuildId,
+    unbanned_user: &User,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if *guild_id != data.config.bot.guild_id {
+        return Ok(());
+    }
+
+    let embed = default_embed(unbanned_user)
+        .title("Member Unbanned")
+        .thumbnail(unbanned_user.face())
+        .field("User", fdisplay(unbanned_user), false)
+        .field(
+            "Database Member",
+            display_database_member(data, unbanned_user).await,
+            false,
+        );
+
+    send_mod_log(ctx, data, embed).await
+}
+
+async fn display_database_member(data: &Data, user: &User) -> String {
+    match MemberModelController::get_by_id(&data.db_pool, &user.id).await {
+        Ok(Some(member)) if member.departed_at.is_some() => "Departed".to_string(),
+        Ok(Some(member)) if member.trial_member => "Yes (Trial)".to_string(),
+        Ok(Some(_)) => "Yes".to_string(),
+        Ok(None) => "No".to_string(),
+        Err(e) => {
+            tracing::error!("Failed to get {} from the database: {e}", display(user));
+            "Unknown".to_string()
+        }
+    }
+}
+
+fn display_nick(nick: Option<&str>) -> String {
+    match nick {
+        Some(nick) => escape_markdown(nick),
+        None => "*None*".to_string(),
+    }
+}
+
+fn display_roles(roles: &[RoleId]) -> String {
+    if roles.is_empty() {
+        return "None".to_string();
+    }
+
+    roles
+        .iter()
+        .map(|role| format!("<@&{role}>"))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+async fn send_member_log(
+    ctx: &serenity::Context,
+    data: &Data,
+    embed: CreateEmbed,
+) -> anyhow::Result<()> {
+    data.config
+        .channels
+        .member_log
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
+
+async fn send_mod_log(
+    ctx: &serenity::Context,
+    data: &Data,
+    embed: CreateEmbed,
+) -> anyhow::Result<()> {
+    data.config
+        .channels
+        .mod_log
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -1,11 +1,35 @@
 use poise::serenity_prelude as serenity;
 
-use crate::util::format;
+use crate::{
+    commands::{
+        application::{handle_application_vote, APPLICATION_VOTE_PREFIX},
+        rolepanel::{handle_role_panel_interaction, ROLE_PANEL_PREFIX},
+    },
+    util::format,
+    Data,
+};
 
 pub async fn handle_interaction_create(
     interaction: &serenity::Interaction,
     ctx: &serenity::Context,
+    data: &Data,
 ) -> anyhow::Result<()> {
+    if let Some(component) = interaction.as_message_component() {
+        if component
+            .data
+            .custom_id
+            .starts_with(APPLICATION_VOTE_PREFIX)
+        {
+            return handle_application_vote(component, ctx, data).await;
+        }
+
+        if component.data.custom_id.starts_with(ROLE_PANEL_PREFIX) {
+            return handle_role_panel_interaction(component, ctx, data).await;
+        }
+
+        return Ok(());
+    }
+
     if interaction.kind() != serenity::InteractionType::Command {
         return Ok(());
     }
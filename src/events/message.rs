@@ -0,0 +1,82 @@
+use poise::serenity_prelude as serenity;
+
+use crate::{
+    commands::run::is_admin,
+    jobs::bridge::relay_to_minecraft,
+    util::{
+        format::{block_code, truncate},
+        rcon::run_rcon_command,
+    },
+    Data,
+};
+
+pub async fn handle_message(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if message.author.bot || message.content.trim().is_empty() {
+        return Ok(());
+    }
+
+    if let Some(server_config) = data
+        .config
+        .minecraft
+        .iter()
+        .find(|s| s.chat_channel == Some(message.channel_id))
+    {
+        return relay_to_minecraft(message, ctx, data, server_config).await;
+    }
+
+    let Some(server_config) = data
+        .config
+        .minecraft
+        .iter()
+        .find(|s| s.console_channel == Some(message.channel_id))
+    else {
+        return Ok(());
+    };
+
+    let member = message.member(ctx).await?;
+
+    if !is_admin(&member, &ctx.cache) {
+        message
+            .reply(
+                ctx,
+                format!("You must be an admin to run commands on {server_config}!"),
+            )
+            .await?;
+        return Ok(());
+    }
+
+    let command = message.content.trim().trim_start_matches('/');
+
+    let response = run_rcon_command(&data.rcon, server_config, vec![command])
+        .await
+        .into_iter()
+        .next();
+
+    let response = match response {
+        Some(Ok(response)) => {
+            response.unwrap_or("Command ran successfully but there was no response.".to_string())
+        }
+        Some(Err(e)) => {
+            tracing::error!("Failed to forward console command to {server_config}: {e}");
+            format!("Failed to run the command on {server_config}.")
+        }
+        None => "Encountered unexpected response from the server.".to_string(),
+    };
+
+    let response = if response.chars().count() > 2000 {
+        format!(
+            "{}\n\nResponse was too long and was truncated.",
+            truncate(&response, 1950)
+        )
+    } else {
+        response
+    };
+
+    message.reply(ctx, block_code(response)).await?;
+
+    Ok(())
+}
@@ -0,0 +1,220 @@
+use poise::serenity_prelude as serenity;
+use serenity::{
+    Attachment, ChannelId, CreateEmbed, CreateEmbedAuthor, CreateMessage, GuildId, Message,
+    MessageId, MessageUpdateEvent, User,
+};
+
+use crate::{
+    util::{
+        builder::log_embed,
+        format::{escape_markdown, fdisplay, truncate},
+    },
+    Data,
+};
+
+const MAX_FIELD_LENGTH: usize = 1024;
+
+pub async fn handle_message_update(
+    old_if_available: &Option<Message>,
+    new: &Option<Message>,
+    event: &MessageUpdateEvent,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if !should_log(data, event.guild_id, event.channel_id) {
+        return Ok(());
+    }
+
+    let Some(after) = new
+        .as_ref()
+        .map(|m| m.content.clone())
+        .or(event.content.clone())
+    else {
+        return Ok(());
+    };
+
+    let before = old_if_available.as_ref().map(|m| m.content.clone());
+
+    // embeds being resolved for links also trigger updates, only log actual edits
+    if before.as_ref() == Some(&after) {
+        return Ok(());
+    }
+
+    let Some(author) = new
+        .as_ref()
+        .map(|m| m.author.clone())
+        .or(event.author.clone())
+    else {
+        return Ok(());
+    };
+
+    if author.bot {
+        return Ok(());
+    }
+
+    let before = match before {
+        Some(before) => display_content(&before),
+        None => "*Not cached*".to_string(),
+    };
+
+    let embed = message_embed(&author, event.channel_id, event.id)
+        .title("Message Edited")
+        .field("Before", before, false)
+        .field("After", display_content(&after), false)
+        .field(
+            "Jump To Message",
+            event.id.link(event.channel_id, event.guild_id),
+            false,
+        );
+
+    let embed = match new {
+        Some(message) if !message.attachments.is_empty() => embed.field(
+            "Attachments",
+            display_attachments(&message.attachments),
+            false,
+        ),
+        _ => embed,
+    };
+
+    send_mod_log(ctx, data, embed).await
+}
+
+pub async fn handle_message_delete(
+    channel_id: &ChannelId,
+    deleted_message_id: &MessageId,
+    guild_id: &Option<GuildId>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if !should_log(data, *guild_id, *channel_id) {
+        return Ok(());
+    }
+
+    // the cache keeps deleted messages around, so the content is still available here
+    let message = ctx
+        .cache
+        .message(channel_id, deleted_message_id)
+        .map(|m| m.clone());
+
+    let embed = match message {
+        Some(message) => {
+            if message.author.bot {
+                return Ok(());
+            }
+
+            let embed = message_embed(&message.author, *channel_id, *deleted_message_id)
+                .title("Message Deleted")
+                .field("Content", display_content(&message.content), false);
+
+            if message.attachments.is_empty() {
+                embed
+            } else {
+                embed.field(
+                    "Attachments",
+                    display_attachments(&message.attachments),
+                    false,
+                )
+            }
+        }
+        None => log_embed()
+            .title("Message Deleted")
+            .field("Channel", format!("<#{channel_id}>"), false)
+            .field("Message ID", deleted_message_id.to_string(), false)
+            .field("Content", "*Not cached*", false),
+    };
+
+    send_mod_log(ctx, data, embed.colour(13_382_451)).await
+}
+
+pub async fn handle_message_delete_bulk(
+    channel_id: &ChannelId,
+    multiple_deleted_messages_ids: &[MessageId],
+    guild_id: &Option<GuildId>,
+    ctx: &serenity::Context,
+    data: &Data,
+) -> anyhow::Result<()> {
+    if !should_log(data, *guild_id, *channel_id) {
+        return Ok(());
+    }
+
+    let mut lines = Vec::new();
+
+    for message_id in multiple_deleted_messages_ids {
+        let Some(message) = ctx.cache.message(channel_id, message_id).map(|m| m.clone()) else {
+            continue;
+        };
+
+        lines.push(format!(
+            "**{}**: {}",
+            escape_markdown(message.author.name),
+            truncate(&message.content, 200)
+        ));
+    }
+
+    let description = if lines.is_empty() {
+        "*None of the messages were cached*".to_string()
+    } else {
+        truncate(&lines.join("\n"), 4096)
+    };
+
+    let embed = log_embed()
+        .title(format!(
+            "{} Messages Bulk Deleted",
+            multiple_deleted_messages_ids.len()
+        ))
+        .colour(13_382_451)
+        .description(description)
+        .field("Channel", format!("<#{channel_id}>"), false);
+
+    send_mod_log(ctx, data, embed).await
+}
+
+fn should_log(data: &Data, guild_id: Option<GuildId>, channel_id: ChannelId) -> bool {
+    guild_id == Some(data.config.bot.guild_id)
+        && channel_id != data.config.channels.mod_log
+        && !data
+            .config
+            .message_log
+            .ignored_channels
+            .contains(&channel_id)
+}
+
+fn message_embed(author: &User, channel_id: ChannelId, message_id: MessageId) -> CreateEmbed {
+    log_embed()
+        .author(CreateEmbedAuthor::new(author.name.clone()).icon_url(author.face()))
+        .field("Author", fdisplay(author), false)
+        .field("Channel", format!("<#{channel_id}>"), false)
+        .field("Message ID", message_id.to_string(), false)
+}
+
+fn display_content(content: &str) -> String {
+    if content.is_empty() {
+        "*Empty*".to_string()
+    } else {
+        truncate(content, MAX_FIELD_LENGTH)
+    }
+}
+
+fn display_attachments(attachments: &[Attachment]) -> String {
+    let display = attachments
+        .iter()
+        .map(|a| format!("[{}]({})", a.filename, a.url))
+        .collect::<Vec<String>>()
+        .join("\n");
+
+    truncate(&display, MAX_FIELD_LENGTH)
+}
+
+async fn send_mod_log(
+    ctx: &serenity::Context,
+    data: &Data,
+    embed: CreateEmbed,
+) -> anyhow::Result<()> {
+    data.config
+        .channels
+        .mod_log
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
+pub mod guild_member;
 pub mod interaction_create;
+pub mod message;
+pub mod message_log;
 pub mod ready;
 
 use poise::serenity_prelude as serenity;
@@ -8,14 +11,105 @@ use crate::Data;
 pub async fn event_handler(
     ctx: &serenity::Context,
     event: &serenity::FullEvent,
-    _framework: poise::FrameworkContext<'_, Data, anyhow::Error>,
+    framework: poise::FrameworkContext<'_, Data, anyhow::Error>,
 ) -> anyhow::Result<()> {
     match event {
         serenity::FullEvent::Ready { data_about_bot, .. } => {
             ready::handle_ready(data_about_bot, ctx).await?;
         }
         serenity::FullEvent::InteractionCreate { interaction, .. } => {
-            interaction_create::handle_interaction_create(interaction, ctx).await?;
+            interaction_create::handle_interaction_create(interaction, ctx, framework.user_data)
+                .await?;
+        }
+        serenity::FullEvent::GuildMemberAddition { new_member } => {
+            guild_member::handle_member_addition(new_member, ctx, framework.user_data).await?;
+        }
+        serenity::FullEvent::GuildMemberRemoval {
+            guild_id,
+            user,
+            member_data_if_available,
+        } => {
+            // the log must not keep a leaving member from being offboarded
+            if let Err(e) = guild_member::handle_member_removal(
+                guild_id,
+                user,
+                member_data_if_available,
+                ctx,
+                framework.user_data,
+            )
+            .await
+            {
+                tracing::error!("Failed to log the removal of a member: {e}");
+            }
+
+            guild_member::offboard_member(guild_id, user, ctx, framework.user_data).await?;
+        }
+        serenity::FullEvent::GuildMemberUpdate {
+            old_if_available,
+            event,
+            ..
+        } => {
+            guild_member::handle_member_update(old_if_available, event, ctx, framework.user_data)
+                .await?;
+        }
+        serenity::FullEvent::GuildBanAddition {
+            guild_id,
+            banned_user,
+        } => {
+            guild_member::handle_ban_addition(guild_id, banned_user, ctx, framework.user_data)
+                .await?;
+        }
+        serenity::FullEvent::GuildBanRemoval {
+            guild_id,
+            unbanned_user,
+        } => {
+            guild_member::handle_ban_removal(guild_id, unbanned_user, ctx, framework.user_data)
+                .await?;
+        }
+        serenity::FullEvent::MessageUpdate {
+            old_if_available,
+            new,
+            event,
+        } => {
+            message_log::handle_message_update(
+                old_if_available,
+                new,
+                event,
+                ctx,
+                framework.user_data,
+            )
+            .await?;
+        }
+        serenity::FullEvent::MessageDelete {
+            channel_id,
+            deleted_message_id,
+            guild_id,
+        } => {
+            message_log::handle_message_delete(
+                channel_id,
+                deleted_message_id,
+                guild_id,
+                ctx,
+                framework.user_data,
+            )
+            .await?;
+        }
+        serenity::FullEvent::MessageDeleteBulk {
+            channel_id,
+            multiple_deleted_messages_ids,
+            guild_id,
+        } => {
+            message_log::handle_message_delete_bulk(
+                channel_id,
+                multiple_deleted_messages_ids,
+                guild_id,
+                ctx,
+                framework.user_data,
+            )
+            .await?;
+        }
+        serenity::FullEvent::Message { new_message } => {
+            message::handle_message(new_message, ctx, framework.user_data).await?;
         }
         _ => {}
     }
@@ -0,0 +1,288 @@
+use std::{cmp::Reverse, collections::HashSet, time::Duration};
+
+use anyhow::Context;
+use poise::serenity_prelude as serenity;
+use pterodactyl_api::client::{backups::Backup, ServerState};
+use serenity::CreateMessage;
+use tokio::time::{Instant, MissedTickBehavior};
+use uuid::Uuid;
+
+use crate::{
+    config::{RetentionConfig, ServerConfig},
+    util::{
+        builder::log_embed,
+        format::inline_code,
+        pterodactyl::{DisplayServerState, PteroClient},
+    },
+    Data,
+};
+
+/// Backups created by the schedule are recognized by their name, retention only applies to them.
+const SCHEDULED_BACKUP_NAME: &str = "Scheduled Backup";
+
+struct BackupSummary {
+    created: Option<Backup>,
+    skipped: Option<String>,
+    deleted: Vec<String>,
+}
+
+pub fn spawn(ctx: &serenity::Context, data: &Data) {
+    for server_config in data.config.minecraft.iter() {
+        let Some(schedule) = server_config.backup_schedule.clone() else {
+            continue;
+        };
+
+        if schedule.interval_hours == 0 {
+            tracing::warn!(
+                "Backup schedule for {server_config} has an interval of 0 hours, skipping."
+            );
+            continue;
+        }
+
+        let ctx = ctx.clone();
+        let data = data.clone();
+        let server_config = server_config.clone();
+
+        tokio::spawn(async move {
+            let period = Duration::from_secs(schedule.interval_hours * 60 * 60);
+            let delay = first_backup_delay(&data, &server_config, period).await;
+            let mut interval = tokio::time::interval_at(Instant::now() + delay, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            tracing::info!(
+                "Scheduled backups for {server_config} every {} hours.",
+                schedule.interval_hours
+            );
+
+            loop {
+                interval.tick().await;
+
+                let summary =
+                    run_scheduled_backup(&ctx, &data, &server_config, &schedule.retention).await;
+
+                if let Err(e) = send_backup_summary(&ctx, &data, &server_config, summary).await {
+                    tracing::error!("Failed to send backup summary for {server_config}: {e}");
+                }
+            }
+        });
+    }
+}
+
+/// Continues the schedule from the newest scheduled backup, so restarting the bot does not push
+/// the next backup back.
+async fn first_backup_delay(
+    data: &Data,
+    server_config: &ServerConfig,
+    period: Duration,
+) -> Duration {
+    let backups = match PteroClient::backup_list(&data.config.pterodactyl, server_config).await {
+        Ok(backups) => backups,
+        Err(e) => {
+            tracing::warn!("Failed to get the backups of {server_config}, backing up now: {e}");
+            return Duration::ZERO;
+        }
+    };
+
+    let Some(newest) = backups
+        .iter()
+        .filter(|b| is_scheduled(b))
+        .max_by_key(|b| b.created_at)
+    else {
+        return Duration::ZERO;
+    };
+
+    let elapsed = chrono::Utc::now().timestamp() - newest.created_at.unix_timestamp();
+
+    period.saturating_sub(Duration::from_secs(elapsed.max(0) as u64))
+}
+
+fn is_scheduled(backup: &Backup) -> bool {
+    backup.name.starts_with(SCHEDULED_BACKUP_NAME)
+}
+
+async fn run_scheduled_backup(
+    ctx: &serenity::Context,
+    data: &Data,
+    server_config: &ServerConfig,
+    retention: &RetentionConfig,
+) -> anyhow::Result<BackupSummary> {
+    let ptero_config = &data.config.pterodactyl;
+
+    let mut summary = BackupSummary {
+        created: None,
+        skipped: None,
+        deleted: Vec::new(),
+    };
+
+    if server_config.backup_limit == 0 {
+        summary.skipped = Some(format!("{server_config} does not allow backups"));
+        return Ok(summary);
+    }
+
+    let state = PteroClient::server_state(ptero_config, server_config).await?;
+
+    if state != ServerState::Running {
+        summary.skipped = Some(format!(
+            "{server_config} is currently {}",
+            DisplayServerState(state)
+        ));
+        return Ok(summary);
+    }
+
+    let backups = PteroClient::backup_list(ptero_config, server_config).await?;
+
+    if backups.len() >= server_config.backup_limit as usize {
+        // only scheduled backups make room, manual backups are never deleted by the schedule
+        let Some(oldest) = oldest_unlocked_scheduled_backup(&backups) else {
+            summary.skipped = Some(format!(
+                "{server_config} reached its backup limit and has no unlocked scheduled backup to replace"
+            ));
+            return Ok(summary);
+        };
+
+        PteroClient::delete_backup(ptero_config, server_config, oldest.uuid)
+            .await
+            .context("Backup limit is reached and the oldest backup could not be deleted")?;
+
+        summary.deleted.push(oldest.name.clone());
+    }
+
+    let bot_user = ctx.cache.current_user().clone();
+    let name = format!(
+        "{SCHEDULED_BACKUP_NAME} ({})",
+        chrono::Utc::now().format("%Y-%m-%d %H:%M UTC")
+    );
+
+    let created = PteroClient::create_backup_and_wait(
+        ptero_config,
+        server_config,
+        Some(name),
+        None,
+        &bot_user,
+    )
+    .await?;
+
+    summary.created = Some(created);
+
+    let backups = PteroClient::backup_list(ptero_config, server_config).await?;
+
+    for expired in select_expired_backups(&backups, retention) {
+        match PteroClient::delete_backup(ptero_config, server_config, expired.uuid).await {
+            Ok(_) => summary.deleted.push(expired.name.clone()),
+            Err(e) => tracing::error!("Failed to apply backup retention on {server_config}: {e}"),
+        }
+    }
+
+    Ok(summary)
+}
+
+fn oldest_unlocked_scheduled_backup(backups: &[Backup]) -> Option<&Backup> {
+    backups
+        .iter()
+        .filter(|b| is_scheduled(b) && !b.is_locked)
+        .min_by_key(|b| b.created_at)
+}
+
+/// Returns every unlocked, completed scheduled backup that is not covered by the retention policy.
+/// Manually created backups are left alone.
+fn select_expired_backups<'a>(
+    backups: &'a [Backup],
+    retention: &RetentionConfig,
+) -> Vec<&'a Backup> {
+    if retention.is_empty() {
+        return Vec::new();
+    }
+
+    let mut candidates = backups
+        .iter()
+        .filter(|b| is_scheduled(b) && !b.is_locked && b.completed_at.is_some())
+        .collect::<Vec<&Backup>>();
+
+    candidates.sort_by_key(|b| Reverse(b.created_at));
+
+    let mut keep: HashSet<Uuid> = candidates
+        .iter()
+        .take(retention.keep_last.max(1))
+        .map(|b| b.uuid)
+        .collect();
+
+    let mut days = HashSet::new();
+    let mut weeks = HashSet::new();
+
+    for backup in &candidates {
+        let day = backup.created_at.date();
+        let (year, week, _) = backup.created_at.to_iso_week_date();
+        let week = (year, week);
+
+        if days.len() < retention.keep_daily && days.insert(day) {
+            keep.insert(backup.uuid);
+        }
+
+        if weeks.len() < retention.keep_weekly && weeks.insert(week) {
+            keep.insert(backup.uuid);
+        }
+    }
+
+    candidates
+        .into_iter()
+        .filter(|b| !keep.contains(&b.uuid))
+        .collect()
+}
+
+async fn send_backup_summary(
+    ctx: &serenity::Context,
+    data: &Data,
+    server_config: &ServerConfig,
+    summary: anyhow::Result<BackupSummary>,
+) -> anyhow::Result<()> {
+    let embed = log_embed().title(format!("Scheduled Backup {server_config}"));
+
+    let embed = match summary {
+        Ok(summary) => {
+            let created = match (summary.created, summary.skipped) {
+                (Some(backup), _) if backup.completed_at.is_some() => format!(
+                    "{} ({})",
+                    inline_code(backup.name),
+                    inline_code(backup.uuid)
+                ),
+                (Some(backup), _) => format!(
+                    "{} ({}) but failed to wait for completion",
+                    inline_code(backup.name),
+                    inline_code(backup.uuid)
+                ),
+                (None, Some(reason)) => format!("Skipped: {reason}"),
+                (None, None) => "None".to_string(),
+            };
+
+            let deleted = if summary.deleted.is_empty() {
+                "None".to_string()
+            } else {
+                summary
+                    .deleted
+                    .into_iter()
+                    .map(inline_code)
+                    .collect::<Vec<String>>()
+                    .join("\n")
+            };
+
+            embed
+                .field("Created", created, false)
+                .field("Deleted", deleted, false)
+        }
+        Err(e) => {
+            tracing::error!("Scheduled backup for {server_config} failed: {e:#}");
+
+            embed
+                .colour(13_382_451)
+                .description(format!("Scheduled backup failed: {e}"))
+        }
+    };
+
+    data.config
+        .channels
+        .bot_log
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -0,0 +1,305 @@
+use std::{collections::HashSet, time::Duration};
+
+use poise::serenity_prelude as serenity;
+use regex::Regex;
+use serenity::{ChannelId, CreateAllowedMentions, CreateMessage};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{
+    commands::status::get_online_players,
+    config::ServerConfig,
+    util::{
+        format::{escape_markdown, truncate},
+        rcon::run_rcon_command,
+    },
+    Data,
+};
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_MESSAGE_LENGTH: usize = 2000;
+const MAX_INGAME_MESSAGE_LENGTH: usize = 256;
+
+const DEATH_MESSAGES: [&str; 19] = [
+    " was ",
+    " died",
+    " drowned",
+    " fell ",
+    " blew up",
+    " burned to death",
+    " hit the ground too hard",
+    " starved to death",
+    " suffocated in a wall",
+    " withered away",
+    " froze to death",
+    " went up in flames",
+    " walked into ",
+    " experienced kinetic energy",
+    " tried to swim in lava",
+    " discovered the floor was lava",
+    " didn't want to live",
+    " left the confines of this world",
+    " went off with a bang",
+];
+
+#[derive(Debug, PartialEq)]
+enum BridgeEvent {
+    Chat { player: String, message: String },
+    Join { player: String },
+    Leave { player: String },
+    Death { message: String },
+    Advancement { player: String, advancement: String },
+}
+
+struct LogParser {
+    prefix: Regex,
+    chat: Regex,
+    join: Regex,
+    leave: Regex,
+    advancement: Regex,
+    online: HashSet<String>,
+}
+
+pub fn spawn(ctx: &serenity::Context, data: &Data) {
+    for server_config in data.config.minecraft.iter() {
+        let Some(channel_id) = server_config.chat_channel else {
+            continue;
+        };
+
+        let Some(receiver) = data.console.subscribe(server_config) else {
+            continue;
+        };
+
+        data.console
+            .connect(&data.config.pterodactyl, server_config);
+
+        let ctx = ctx.clone();
+        let data = data.clone();
+        let server_config = server_config.clone();
+
+        tokio::spawn(async move {
+            if let Err(e) = relay_to_discord(ctx, &data, &server_config, channel_id, receiver).await
+            {
+                tracing::error!("Chat bridge for {server_config} stopped: {e}");
+            }
+        });
+    }
+}
+
+async fn relay_to_discord(
+    ctx: serenity::Context,
+    data: &Data,
+    server_config: &ServerConfig,
+    channel_id: ChannelId,
+    mut receiver: broadcast::Receiver<String>,
+) -> anyhow::Result<()> {
+    let mut parser = LogParser::new()?;
+    seed_online_players(&mut parser, data, server_config).await;
+
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    let mut pending: Vec<String> = Vec::new();
+
+    loop {
+        tokio::select! {
+            line = receiver.recv() => match line {
+                Ok(line) => {
+                    if let Some(event) = parser.parse(&line) {
+                        pending.push(display_event(event));
+                    }
+                }
+                Err(RecvError::Lagged(count)) => {
+                    tracing::warn!("Chat bridge for {server_config} skipped {count} console lines.");
+                    // the skipped lines may have contained joins and leaves
+                    seed_online_players(&mut parser, data, server_config).await;
+                }
+                Err(RecvError::Closed) => break,
+            },
+            _ = interval.tick() => {
+                if pending.is_empty() {
+                    continue;
+                }
+
+                for content in join_messages(std::mem::take(&mut pending)) {
+                    let message = CreateMessage::new()
+                        .content(content)
+                        .allowed_mentions(CreateAllowedMentions::new());
+
+                    if let Err(e) = channel_id.send_message(&ctx, message).await {
+                        tracing::error!("Failed to relay chat from {server_config}: {e}");
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Players that were online before the bridge started never show up in a join line, so the parser
+/// learns about them from the player list instead.
+async fn seed_online_players(parser: &mut LogParser, data: &Data, server_config: &ServerConfig) {
+    match get_online_players(&data.rcon, server_config).await {
+        Ok(players) => parser.online = players.into_iter().map(|p| p.name).collect(),
+        Err(e) => {
+            tracing::warn!(
+                "Failed to get the players online on {server_config} for the chat bridge: {e:#}"
+            );
+        }
+    }
+}
+
+/// Sends a discord message to everyone on the server using tellraw.
+pub async fn relay_to_minecraft(
+    message: &serenity::Message,
+    ctx: &serenity::Context,
+    data: &Data,
+    server_config: &ServerConfig,
+) -> anyhow::Result<()> {
+    let author = message
+        .author_nick(ctx)
+        .await
+        .or(message.author.global_name.clone())
+        .unwrap_or(message.author.name.clone());
+
+    let mut content = message.content_safe(&ctx.cache).replace(['\n', '\r'], " ");
+
+    if !message.attachments.is_empty() {
+        content.push_str(" [attachment]");
+    }
+
+    let content = truncate(content.trim(), MAX_INGAME_MESSAGE_LENGTH);
+
+    if content.is_empty() {
+        return Ok(());
+    }
+
+    let command = format!("tellraw @a {}", tellraw_component(&author, &content)?);
+
+    run_rcon_command(&data.rcon, server_config, vec![command])
+        .await
+        .into_iter()
+        .next()
+        .unwrap_or(Ok(None))?;
+
+    Ok(())
+}
+
+/// Builds the JSON text component for tellraw. Serializing through serde_json takes care of escaping
+/// quotes, backslashes and control characters, so user input can never break out of the text.
+fn tellraw_component(author: &str, content: &str) -> anyhow::Result<String> {
+    let component = serde_json::json!([
+        "",
+        { "text": "[Discord] ", "color": "blue" },
+        { "text": format!("<{author}> "), "color": "white" },
+        { "text": content, "color": "white" }
+    ]);
+
+    Ok(serde_json::to_string(&component)?)
+}
+
+fn display_event(event: BridgeEvent) -> String {
+    match event {
+        BridgeEvent::Chat { player, message } => {
+            format!(
+                "**{}**: {}",
+                escape_markdown(player),
+                escape_markdown(message)
+            )
+        }
+        BridgeEvent::Join { player } => format!("*{} joined the game*", escape_markdown(player)),
+        BridgeEvent::Leave { player } => format!("*{} left the game*", escape_markdown(player)),
+        BridgeEvent::Death { message } => format!("*{}*", escape_markdown(message)),
+        BridgeEvent::Advancement {
+            player,
+            advancement,
+        } => format!(
+            "*{} has made the advancement* **{}**",
+            escape_markdown(player),
+            escape_markdown(advancement)
+        ),
+    }
+}
+
+fn join_messages(lines: Vec<String>) -> Vec<String> {
+    let mut messages = Vec::new();
+    let mut current = String::new();
+
+    for line in lines {
+        let line = truncate(&line, MAX_MESSAGE_LENGTH - 1);
+
+        if current.chars().count() + line.chars().count() + 1 > MAX_MESSAGE_LENGTH {
+            messages.push(std::mem::take(&mut current));
+        }
+
+        if !current.is_empty() {
+            current.push('\n');
+        }
+
+        current.push_str(&line);
+    }
+
+    if !current.is_empty() {
+        messages.push(current);
+    }
+
+    messages
+}
+
+impl LogParser {
+    fn new() -> anyhow::Result<Self> {
+        Ok(Self {
+            prefix: Regex::new(r"^\[[^\]]+\] \[Server thread/INFO\]: (?:\[[^\]]*\] )?(.*)$")?,
+            chat: Regex::new(r"^<([A-Za-z0-9_]{1,16})> (.*)$")?,
+            join: Regex::new(r"^([A-Za-z0-9_]{1,16}) joined the game$")?,
+            leave: Regex::new(r"^([A-Za-z0-9_]{1,16}) left the game$")?,
+            advancement: Regex::new(
+                r"^([A-Za-z0-9_]{1,16}) has (?:made the advancement|completed the challenge|reached the goal) \[(.*)\]$",
+            )?,
+            online: HashSet::new(),
+        })
+    }
+
+    fn parse(&mut self, line: &str) -> Option<BridgeEvent> {
+        let content = self.prefix.captures(line.trim())?.get(1)?.as_str();
+
+        if let Some(captures) = self.chat.captures(content) {
+            return Some(BridgeEvent::Chat {
+                player: captures[1].to_string(),
+                message: captures[2].to_string(),
+            });
+        }
+
+        if let Some(captures) = self.join.captures(content) {
+            let player = captures[1].to_string();
+            self.online.insert(player.clone());
+            return Some(BridgeEvent::Join { player });
+        }
+
+        if let Some(captures) = self.leave.captures(content) {
+            let player = captures[1].to_string();
+            self.online.remove(&player);
+            return Some(BridgeEvent::Leave { player });
+        }
+
+        if let Some(captures) = self.advancement.captures(content) {
+            return Some(BridgeEvent::Advancement {
+                player: captures[1].to_string(),
+                advancement: captures[2].to_string(),
+            });
+        }
+
+        // death messages have no common format, so only trust them for players we saw joining
+        let player = content.split(' ').next()?;
+
+        if self.online.contains(player)
+            && DEATH_MESSAGES
+                .iter()
+                .any(|death| content[player.len()..].starts_with(death))
+        {
+            return Some(BridgeEvent::Death {
+                message: content.to_string(),
+            });
+        }
+
+        None
+    }
+}
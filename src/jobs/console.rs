@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use serenity::{ChannelId, CreateMessage};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::{config::ServerConfig, util::format::block_code, Data};
+
+const FLUSH_INTERVAL: Duration = Duration::from_secs(2);
+const MAX_MESSAGES_PER_FLUSH: usize = 2;
+const MAX_MESSAGE_LENGTH: usize = 1900;
+
+pub fn spawn(ctx: &serenity::Context, data: &Data) {
+    for server_config in data.config.minecraft.iter() {
+        let Some(channel_id) = server_config.console_channel else {
+            continue;
+        };
+
+        let Some(receiver) = data.console.subscribe(server_config) else {
+            continue;
+        };
+
+        data.console
+            .connect(&data.config.pterodactyl, server_config);
+
+        let ctx = ctx.clone();
+        let server_config = server_config.clone();
+
+        tokio::spawn(async move {
+            mirror_console(ctx, server_config, channel_id, receiver).await;
+        });
+    }
+}
+
+async fn mirror_console(
+    ctx: serenity::Context,
+    server_config: ServerConfig,
+    channel_id: ChannelId,
+    mut receiver: broadcast::Receiver<String>,
+) {
+    let mut interval = tokio::time::interval(FLUSH_INTERVAL);
+    let mut pending: Vec<String> = Vec::new();
+    let mut skipped = 0;
+
+    loop {
+        tokio::select! {
+            line = receiver.recv() => match line {
+                Ok(line) => pending.push(line),
+                Err(RecvError::Lagged(count)) => skipped += count as usize,
+                Err(RecvError::Closed) => break,
+            },
+            _ = interval.tick() => {
+                if pending.is_empty() && skipped == 0 {
+                    continue;
+                }
+
+                let lines = std::mem::take(&mut pending);
+                let mut chunks = chunk_lines(lines);
+
+                if chunks.len() > MAX_MESSAGES_PER_FLUSH {
+                    skipped += chunks
+                        .drain(MAX_MESSAGES_PER_FLUSH..)
+                        .map(|chunk| chunk.lines().count())
+                        .sum::<usize>();
+                }
+
+                for chunk in chunks {
+                    let message = CreateMessage::new().content(block_code(format!("\n{chunk}")));
+
+                    if let Err(e) = channel_id.send_message(&ctx, message).await {
+                        tracing::error!("Failed to mirror console of {server_config}: {e}");
+                    }
+                }
+
+                if skipped > 0 {
+                    let message = CreateMessage::new()
+                        .content(format!("*Skipped {skipped} console lines to stay within rate limits.*"));
+
+                    if let Err(e) = channel_id.send_message(&ctx, message).await {
+                        tracing::error!("Failed to mirror console of {server_config}: {e}");
+                    }
+
+                    skipped = 0;
+                }
+            }
+        }
+    }
+}
+
+/// Joins console lines into chunks that fit into a single code block message.
+fn chunk_lines(lines: Vec<String>) -> Vec<String> {
+    let mut chunks = Vec::new();
+    let mut current = String::new();
+
+    for line in lines {
+        let mut line = line.replace("```", "`\u{200b}``");
+
+        if line.len() > MAX_MESSAGE_LENGTH {
+            let mut end = MAX_MESSAGE_LENGTH;
+
+            while !line.is_char_boundary(end) {
+                end -= 1;
+            }
+
+            line.truncate(end);
+        }
+
+        if current.len() + line.len() + 1 > MAX_MESSAGE_LENGTH {
+            chunks.push(std::mem::take(&mut current));
+        }
+
+        current.push_str(&line);
+        current.push('\n');
+    }
+
+    if !current.is_empty() {
+        chunks.push(current);
+    }
+
+    chunks
+}
@@ -0,0 +1,250 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use pterodactyl_api::client::ServerState;
+use serenity::{CreateAllowedMentions, CreateMessage};
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::{
+    commands::status::{calculate_embed_color, get_server_performance},
+    config::{LagAlertConfig, LagWatchdogConfig, ServerConfig},
+    util::{
+        builder::log_embed,
+        format::{time, TimestampStyle},
+        pterodactyl::{DisplayServerState, PteroClient},
+    },
+    Data,
+};
+
+#[derive(Default)]
+struct LagState {
+    /// Samples in a row on the other side of the threshold than the current state.
+    streak: u32,
+    streak_since: Option<chrono::DateTime<chrono::Utc>>,
+    /// `None` while the server is not lagging.
+    lagging: Option<Lag>,
+    last_alert: Option<Instant>,
+}
+
+struct Lag {
+    since: chrono::DateTime<chrono::Utc>,
+    peak_mspt: u32,
+    alerted: bool,
+}
+
+enum LagEvent {
+    Alert { mspt: u32 },
+    Recovered { mspt: u32, lag: Lag },
+    Stopped { state: ServerState, lag: Lag },
+}
+
+pub fn spawn(ctx: &serenity::Context, data: &Data) {
+    let Some(watchdog_config) = data.config.lag_watchdog.clone() else {
+        return;
+    };
+
+    if watchdog_config.interval_seconds == 0 {
+        tracing::warn!("Lag watchdog has an interval of 0 seconds, skipping.");
+        return;
+    }
+
+    for server_config in data.config.minecraft.iter() {
+        let Some(alert_config) = server_config.lag_alert.clone() else {
+            continue;
+        };
+
+        let ctx = ctx.clone();
+        let data = data.clone();
+        let server_config = server_config.clone();
+        let watchdog_config = watchdog_config.clone();
+
+        tokio::spawn(async move {
+            let period = Duration::from_secs(watchdog_config.interval_seconds);
+            let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+            interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+            tracing::info!(
+                "Watching {server_config} for MSPT above {} every {} seconds.",
+                alert_config.mspt_threshold,
+                watchdog_config.interval_seconds
+            );
+
+            let mut state = LagState::default();
+
+            loop {
+                interval.tick().await;
+
+                let event = match poll(
+                    &data,
+                    &server_config,
+                    &alert_config,
+                    &watchdog_config,
+                    &mut state,
+                )
+                .await
+                {
+                    Ok(Some(event)) => event,
+                    Ok(None) => continue,
+                    Err(e) => {
+                        tracing::warn!("Failed to poll the MSPT of {server_config}: {e:#}");
+                        continue;
+                    }
+                };
+
+                if let Err(e) = send_lag_event(
+                    &ctx,
+                    &data,
+                    &watchdog_config,
+                    &server_config,
+                    &alert_config,
+                    event,
+                )
+                .await
+                {
+                    tracing::error!("Failed to send a lag alert for {server_config}: {e}");
+                }
+            }
+        });
+    }
+}
+
+async fn poll(
+    data: &Data,
+    server_config: &ServerConfig,
+    alert_config: &LagAlertConfig,
+    watchdog_config: &LagWatchdogConfig,
+    state: &mut LagState,
+) -> anyhow::Result<Option<LagEvent>> {
+    let server_state = PteroClient::server_state(&data.config.pterodactyl, server_config).await?;
+
+    if server_state != ServerState::Running {
+        state.streak = 0;
+        state.streak_since = None;
+
+        return Ok(state
+            .lagging
+            .take()
+            .filter(|lag| lag.alerted)
+            .map(|lag| LagEvent::Stopped {
+                state: server_state,
+                lag,
+            }));
+    }
+
+    let mspt = get_server_performance(&data.rcon, server_config)
+        .await?
+        .mspt;
+
+    let above = mspt > alert_config.mspt_threshold;
+
+    match (&mut state.lagging, above) {
+        (None, true) | (Some(_), false) => {
+            state.streak += 1;
+            state.streak_since.get_or_insert_with(chrono::Utc::now);
+        }
+        (Some(lag), true) => {
+            lag.peak_mspt = lag.peak_mspt.max(mspt);
+            state.streak = 0;
+            state.streak_since = None;
+        }
+        (None, false) => {
+            state.streak = 0;
+            state.streak_since = None;
+        }
+    }
+
+    if state.streak < alert_config.samples.max(1) {
+        return Ok(None);
+    }
+
+    state.streak = 0;
+    let streak_since = state.streak_since.take().unwrap_or_else(chrono::Utc::now);
+
+    let Some(lag) = state.lagging.take() else {
+        let cooldown = Duration::from_secs(watchdog_config.cooldown_minutes * 60);
+        let alerted = state
+            .last_alert
+            .is_none_or(|last_alert| last_alert.elapsed() >= cooldown);
+
+        if alerted {
+            state.last_alert = Some(Instant::now());
+        }
+
+        state.lagging = Some(Lag {
+            since: streak_since,
+            peak_mspt: mspt,
+            alerted,
+        });
+
+        return Ok(alerted.then_some(LagEvent::Alert { mspt }));
+    };
+
+    // lags that were not announced because of the cooldown recover silently
+    Ok(lag.alerted.then_some(LagEvent::Recovered { mspt, lag }))
+}
+
+async fn send_lag_event(
+    ctx: &serenity::Context,
+    data: &Data,
+    watchdog_config: &LagWatchdogConfig,
+    server_config: &ServerConfig,
+    alert_config: &LagAlertConfig,
+    event: LagEvent,
+) -> anyhow::Result<()> {
+    let message = match event {
+        LagEvent::Alert { mspt } => {
+            let embed = log_embed()
+                .title(format!("{server_config} Is Lagging"))
+                .colour(calculate_embed_color(mspt))
+                .description(format!(
+                    "MSPT has been above **{}** for {} samples in a row.",
+                    alert_config.mspt_threshold, alert_config.samples
+                ))
+                .field("MSPT", format!("**{mspt}**"), false);
+
+            CreateMessage::new()
+                .content(format!("<@&{}>", watchdog_config.role))
+                .embed(embed)
+                .allowed_mentions(CreateAllowedMentions::new().roles(vec![watchdog_config.role]))
+        }
+        LagEvent::Recovered { mspt, lag } => {
+            let embed = log_embed()
+                .title(format!("{server_config} Recovered"))
+                .colour(calculate_embed_color(mspt))
+                .description(format!(
+                    "MSPT is back below **{}**.",
+                    alert_config.mspt_threshold
+                ))
+                .field("MSPT", format!("**{mspt}**"), false)
+                .field("Peak MSPT", format!("**{}**", lag.peak_mspt), false)
+                .field(
+                    "Lagging Since",
+                    time(lag.since, TimestampStyle::Relative),
+                    false,
+                );
+
+            CreateMessage::new().embed(embed)
+        }
+        LagEvent::Stopped { state, lag } => {
+            let embed = log_embed()
+                .title(format!("{server_config} Lag Alert Cleared"))
+                .description(format!("The server is {} now.", DisplayServerState(state)))
+                .field("Peak MSPT", format!("**{}**", lag.peak_mspt), false)
+                .field(
+                    "Lagging Since",
+                    time(lag.since, TimestampStyle::Relative),
+                    false,
+                );
+
+            CreateMessage::new().embed(embed)
+        }
+    };
+
+    data.config
+        .channels
+        .bot_log
+        .send_message(ctx, message)
+        .await?;
+
+    Ok(())
+}
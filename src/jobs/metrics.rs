@@ -0,0 +1,80 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use pterodactyl_api::client::ServerState;
+use tokio::time::{Instant, MissedTickBehavior};
+
+use crate::{
+    commands::status::get_server_metrics,
+    config::{MetricsConfig, ServerConfig},
+    database::model::server_metrics::ServerMetricsModelController,
+    util::pterodactyl::PteroClient,
+    Data,
+};
+
+pub fn spawn(_ctx: &serenity::Context, data: &Data) {
+    let Some(metrics_config) = data.config.metrics.clone() else {
+        return;
+    };
+
+    if metrics_config.interval_minutes == 0 {
+        tracing::warn!("Metrics sampling has an interval of 0 minutes, skipping.");
+        return;
+    }
+
+    let data = data.clone();
+
+    tokio::spawn(async move {
+        let period = Duration::from_secs(metrics_config.interval_minutes * 60);
+        let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        tracing::info!(
+            "Sampling server metrics every {} minutes.",
+            metrics_config.interval_minutes
+        );
+
+        loop {
+            interval.tick().await;
+
+            for server_config in data.config.minecraft.iter() {
+                if let Err(e) = record_sample(&data, server_config).await {
+                    tracing::warn!("Failed to sample the metrics of {server_config}: {e:#}");
+                }
+            }
+
+            if let Err(e) = prune_samples(&data, &metrics_config).await {
+                tracing::error!("Failed to prune old server metrics: {e:#}");
+            }
+        }
+    });
+}
+
+async fn record_sample(data: &Data, server_config: &ServerConfig) -> anyhow::Result<()> {
+    let state = PteroClient::server_state(&data.config.pterodactyl, server_config).await?;
+
+    // stopped servers have nothing to report, gaps in the history show the downtime
+    if state != ServerState::Running {
+        return Ok(());
+    }
+
+    let metrics = get_server_metrics(&data.rcon, server_config).await?;
+
+    ServerMetricsModelController::create(&data.db_pool, &server_config.server_name, &metrics).await
+}
+
+async fn prune_samples(data: &Data, metrics_config: &MetricsConfig) -> anyhow::Result<()> {
+    let Some(retention_days) = metrics_config.retention_days else {
+        return Ok(());
+    };
+
+    let before = Utc::now() - chrono::Duration::days(retention_days.try_into()?);
+    let deleted = ServerMetricsModelController::prune(&data.db_pool, before).await?;
+
+    if deleted > 0 {
+        tracing::info!("Pruned {deleted} server metrics samples older than {retention_days} days.");
+    }
+
+    Ok(())
+}
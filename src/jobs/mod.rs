@@ -0,0 +1,25 @@
+pub mod backup;
+pub mod bridge;
+pub mod console;
+pub mod lag;
+pub mod metrics;
+pub mod playtime;
+pub mod status_board;
+pub mod trial;
+pub mod whitelist;
+
+use poise::serenity_prelude as serenity;
+
+use crate::Data;
+
+pub fn spawn_jobs(ctx: &serenity::Context, data: &Data) {
+    backup::spawn(ctx, data);
+    bridge::spawn(ctx, data);
+    console::spawn(ctx, data);
+    lag::spawn(ctx, data);
+    metrics::spawn(ctx, data);
+    playtime::spawn(ctx, data);
+    status_board::spawn(ctx, data);
+    trial::spawn(ctx, data);
+    whitelist::spawn(ctx, data);
+}
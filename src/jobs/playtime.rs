@@ -0,0 +1,109 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use pterodactyl_api::client::ServerState;
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    commands::status::get_online_players,
+    config::ServerConfig,
+    database::model::player_session::{PlayerSession, PlayerSessionModelController},
+    util::pterodactyl::PteroClient,
+    Data,
+};
+
+pub fn spawn(_ctx: &serenity::Context, data: &Data) {
+    let data = data.clone();
+
+    tokio::spawn(async move {
+        // sessions that were open while the bot was down cannot be tracked anymore, so they end
+        // at the last time their player was seen
+        match PlayerSessionModelController::end_all_open(&data.db_pool).await {
+            Ok(0) => {}
+            Ok(count) => {
+                tracing::info!("Closed {count} player sessions left open by the last run.")
+            }
+            Err(e) => tracing::warn!("Failed to close the open player sessions: {e:#}"),
+        }
+
+        let Some(playtime_config) = data.config.playtime.clone() else {
+            return;
+        };
+
+        if playtime_config.interval_seconds == 0 {
+            tracing::warn!("Playtime tracking has an interval of 0 seconds, skipping.");
+            return;
+        }
+
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(playtime_config.interval_seconds));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        tracing::info!(
+            "Tracking player sessions every {} seconds.",
+            playtime_config.interval_seconds
+        );
+
+        loop {
+            interval.tick().await;
+
+            for server_config in data.config.minecraft.iter() {
+                if let Err(e) = update_sessions(&data, server_config).await {
+                    tracing::warn!(
+                        "Failed to update the player sessions of {server_config}: {e:#}"
+                    );
+                }
+            }
+        }
+    });
+}
+
+/// Diffs the players online against the open sessions, starting sessions for players that joined
+/// and ending the ones of players that left at the last poll they were seen in. Joins and leaves
+/// are only as precise as the interval.
+async fn update_sessions(data: &Data, server_config: &ServerConfig) -> anyhow::Result<()> {
+    let db_pool = &data.db_pool;
+    let now = Utc::now();
+
+    let open_sessions =
+        PlayerSessionModelController::get_open(db_pool, &server_config.server_name).await?;
+
+    let state = PteroClient::server_state(&data.config.pterodactyl, server_config).await?;
+
+    let online = if state == ServerState::Running {
+        get_online_players(&data.rcon, server_config).await?
+    } else {
+        Vec::new()
+    };
+
+    let (still_online, left): (Vec<&PlayerSession>, Vec<&PlayerSession>) = open_sessions
+        .iter()
+        .partition(|session| online.iter().any(|p| p.uuid == session.minecraft_uuid));
+
+    if !still_online.is_empty() {
+        let ids = still_online.iter().map(|s| s.id).collect::<Vec<i64>>();
+        PlayerSessionModelController::touch(db_pool, &ids, now).await?;
+    }
+
+    if !left.is_empty() {
+        let ids = left.iter().map(|s| s.id).collect::<Vec<i64>>();
+        PlayerSessionModelController::end(db_pool, &ids).await?;
+    }
+
+    for player in online
+        .iter()
+        .filter(|p| !open_sessions.iter().any(|s| s.minecraft_uuid == p.uuid))
+    {
+        PlayerSessionModelController::start(
+            db_pool,
+            &server_config.server_name,
+            &player.uuid,
+            &player.name,
+            now,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use std::time::Duration;
+
+use anyhow::Context;
+use futures_util::future::join_all;
+use poise::serenity_prelude as serenity;
+use serenity::{CreateEmbed, CreateMessage, EditMessage};
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    commands::status::{build_status_board_entry, StatusBoardEntry},
+    database::model::status_board::StatusBoardModelController,
+    util::format::{time, truncate, TimestampStyle},
+    Data,
+};
+
+/// A message can hold at most 10 embeds.
+const MAX_SERVERS: usize = 10;
+
+/// Discord allows at most 6000 characters across all embeds of a message.
+const MAX_EMBEDS_LENGTH: usize = 6000;
+
+/// Discord allows at most 1024 characters in an embed field.
+const MAX_FIELD_LENGTH: usize = 1024;
+
+const PLAYER_LIST_NAME: &str = "Playerlist";
+
+pub fn spawn(ctx: &serenity::Context, data: &Data) {
+    let Some(board_config) = data.config.status_board.clone() else {
+        return;
+    };
+
+    if board_config.interval_seconds == 0 {
+        tracing::warn!("Status board has an interval of 0 seconds, skipping.");
+        return;
+    }
+
+    if data.config.minecraft.iter().count() > MAX_SERVERS {
+        tracing::warn!("The status board only shows the first {MAX_SERVERS} servers.");
+    }
+
+    let ctx = ctx.clone();
+    let data = data.clone();
+
+    tokio::spawn(async move {
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(board_config.interval_seconds));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        tracing::info!(
+            "Updating the status board every {} seconds.",
+            board_config.interval_seconds
+        );
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = update_status_board(&ctx, &data).await {
+                tracing::error!("Failed to update the status board: {e:#}");
+            }
+        }
+    });
+}
+
+/// Edits the status board message, or posts a new one when there is none or it was deleted.
+async fn update_status_board(ctx: &serenity::Context, data: &Data) -> anyhow::Result<()> {
+    let channel_id = data.config.channels.server_info;
+
+    let entries = join_all(
+        data.config
+            .minecraft
+            .iter()
+            .take(MAX_SERVERS)
+            .map(|server_config| build_status_board_entry(data, server_config)),
+    )
+    .await;
+
+    let embeds = fit_player_lists(entries);
+
+    let content = format!(
+        "Last updated {}",
+        time(chrono::Utc::now(), TimestampStyle::Relative)
+    );
+
+    if let Some(message_id) =
+        StatusBoardModelController::get_message(&data.db_pool, channel_id).await?
+    {
+        let edit = EditMessage::new()
+            .content(content.clone())
+            .embeds(embeds.clone());
+
+        match channel_id.edit_message(ctx, message_id, edit).await {
+            Ok(_) => return Ok(()),
+            Err(serenity::Error::Http(e))
+                if e.status_code().is_some_and(|status| status.as_u16() == 404) =>
+            {
+                tracing::info!("The status board message was deleted, posting a new one.");
+            }
+            Err(e) => {
+                // the message may still exist, so posting a new one could leave two boards behind
+                return Err(e).context(format!(
+                    "Failed to edit the status board message {message_id}"
+                ));
+            }
+        }
+    }
+
+    let message = channel_id
+        .send_message(ctx, CreateMessage::new().content(content).embeds(embeds))
+        .await?;
+
+    StatusBoardModelController::set_message(&data.db_pool, channel_id, message.id).await
+}
+
+/// Adds the player lists to the embeds, shortening them so all embeds together stay within the
+/// size limit of a message. Lists are handed their share from the shortest to the longest, so the
+/// space a short list leaves over goes to the longer ones.
+fn fit_player_lists(entries: Vec<StatusBoardEntry>) -> Vec<CreateEmbed> {
+    let used = entries
+        .iter()
+        .map(|entry| embed_length(&entry.embed))
+        .sum::<usize>();
+    let mut budget = MAX_EMBEDS_LENGTH.saturating_sub(used);
+
+    let mut lists = entries
+        .iter()
+        .enumerate()
+        .filter_map(|(i, entry)| entry.player_list.clone().map(|list| (i, list)))
+        .collect::<Vec<(usize, String)>>();
+    lists.sort_by_key(|(_, list)| list.chars().count());
+
+    let mut fitted = vec![None; entries.len()];
+    let mut remaining = lists.len();
+
+    for (i, list) in lists {
+        let share = (budget / remaining)
+            .saturating_sub(PLAYER_LIST_NAME.len())
+            .min(MAX_FIELD_LENGTH);
+        remaining -= 1;
+
+        // leave the field out when there is no room for more than the ellipsis
+        if share <= 3 {
+            continue;
+        }
+
+        let list = truncate(&list, share);
+        budget -= PLAYER_LIST_NAME.len() + list.chars().count();
+        fitted[i] = Some(list);
+    }
+
+    entries
+        .into_iter()
+        .zip(fitted)
+        .map(|(entry, list)| match list {
+            Some(list) => entry.embed.field(PLAYER_LIST_NAME, list, false),
+            None => entry.embed,
+        })
+        .collect()
+}
+
+/// Counts the characters of the embed that Discord counts towards the size limit of a message.
+fn embed_length(embed: &CreateEmbed) -> usize {
+    let value = serde_json::to_value(embed).unwrap_or_default();
+    let length = |value: &serde_json::Value| value.as_str().map_or(0, |s| s.chars().count());
+
+    let fields = value["fields"].as_array().map_or(0, |fields| {
+        fields
+            .iter()
+            .map(|field| length(&field["name"]) + length(&field["value"]))
+            .sum()
+    });
+
+    length(&value["title"])
+        + length(&value["description"])
+        + length(&value["footer"]["text"])
+        + length(&value["author"]["name"])
+        + fields
+}
@@ -0,0 +1,121 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use poise::serenity_prelude as serenity;
+use serenity::{CreateAllowedMentions, CreateMessage};
+use tokio::time::MissedTickBehavior;
+
+use crate::{
+    database::model::member::MemberModelController,
+    util::{
+        builder::log_embed,
+        format::{time, TimestampStyle},
+    },
+    Data,
+};
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+pub fn spawn(ctx: &serenity::Context, data: &Data) {
+    let Some(trial_config) = data.config.trial.clone() else {
+        return;
+    };
+
+    let ctx = ctx.clone();
+    let data = data.clone();
+
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(CHECK_INTERVAL);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        tracing::info!(
+            "Checking for trials due for review every day, trials last {} days.",
+            trial_config.duration_days
+        );
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) = remind_due_trials(&ctx, &data, trial_config.duration_days).await {
+                tracing::error!("Failed to check for trials due for review: {e:#}");
+            }
+        }
+    });
+}
+
+/// Posts the trials that became due since the last check to the mod log. Each trial is only
+/// reported once, unless its member since date is moved past the last reminder.
+async fn remind_due_trials(
+    ctx: &serenity::Context,
+    data: &Data,
+    duration_days: u64,
+) -> anyhow::Result<()> {
+    let duration = chrono::Duration::days(duration_days.try_into()?);
+    let now = Utc::now();
+
+    let mut due = MemberModelController::get_all(&data.db_pool)
+        .await?
+        .into_iter()
+        .filter(|member| {
+            let due_at = member.member_since + duration;
+
+            member.trial_member
+                && member.departed_at.is_none()
+                && due_at <= now
+                && member
+                    .trial_reminded_at
+                    .is_none_or(|reminded_at| reminded_at < due_at)
+        })
+        .collect::<Vec<_>>();
+
+    if due.is_empty() {
+        return Ok(());
+    }
+
+    due.sort_by_key(|member| member.member_since);
+
+    let mut description = String::new();
+    let mut reminded = Vec::new();
+
+    // trials that do not fit into this reminder stay unreminded and are posted in the next one
+    for member in &due {
+        let line = format!(
+            "<@{}>: trial since {} ({} days)\n",
+            member.discord_id,
+            time(member.member_since, TimestampStyle::LongDate),
+            (now - member.member_since).num_days()
+        );
+
+        if description.len() + line.len() > 4000 {
+            description.push_str(&format!("... and {} more", due.len() - reminded.len()));
+            break;
+        }
+
+        description.push_str(&line);
+        reminded.push(member.discord_id);
+    }
+
+    let embed = log_embed()
+        .title("Trials Due For Review")
+        .description(description)
+        .field(
+            "Promote",
+            "Use `/member promote` to make them full members.",
+            false,
+        );
+
+    data.config
+        .channels
+        .mod_log
+        .send_message(
+            ctx,
+            CreateMessage::new()
+                .embed(embed)
+                .allowed_mentions(CreateAllowedMentions::new()),
+        )
+        .await?;
+
+    MemberModelController::mark_trial_reminded(&data.db_pool, &reminded).await?;
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use std::time::Duration;
+
+use poise::serenity_prelude as serenity;
+use serenity::CreateMessage;
+use tokio::time::{Instant, MissedTickBehavior};
+use uuid::Uuid;
+
+use crate::{
+    commands::whitelist::{
+        apply_list_diffs, build_whitelist_diff_embed, compute_list_diffs, Whitelist, WhitelistDiff,
+    },
+    util::builder::log_embed,
+    Data,
+};
+
+pub fn spawn(ctx: &serenity::Context, data: &Data) {
+    let Some(sync_config) = data.config.whitelist_sync.clone() else {
+        return;
+    };
+
+    if sync_config.interval_hours == 0 {
+        tracing::warn!("Whitelist sync has an interval of 0 hours, skipping.");
+        return;
+    }
+
+    let ctx = ctx.clone();
+    let data = data.clone();
+
+    tokio::spawn(async move {
+        let period = Duration::from_secs(sync_config.interval_hours * 60 * 60);
+        let mut interval = tokio::time::interval_at(Instant::now() + period, period);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        tracing::info!(
+            "Scheduled whitelist sync every {} hours.",
+            sync_config.interval_hours
+        );
+
+        // unresolved accounts usually stay unresolved, so they are only reported when they change
+        let mut reported_unresolved = Vec::new();
+
+        loop {
+            interval.tick().await;
+
+            if let Err(e) =
+                run_whitelist_sync(&ctx, &data, sync_config.apply, &mut reported_unresolved).await
+            {
+                tracing::error!("Failed to run the scheduled whitelist sync: {e:#}");
+            }
+        }
+    });
+}
+
+async fn run_whitelist_sync(
+    ctx: &serenity::Context,
+    data: &Data,
+    apply: bool,
+    reported_unresolved: &mut Vec<Uuid>,
+) -> anyhow::Result<()> {
+    let whitelist = Whitelist { rcon: &data.rcon };
+
+    let mut diffs =
+        match compute_list_diffs(&data.db_pool, &data.config.minecraft, &whitelist).await {
+            Ok(diffs) => diffs,
+            Err(e) => {
+                let embed = log_embed()
+                    .title("Scheduled Whitelist Sync")
+                    .colour(13_382_451)
+                    .description(format!("Failed to compute the whitelist changes: {e}"));
+
+                send_log(ctx, data, embed).await?;
+                return Err(e);
+            }
+        };
+
+    let mut unresolved = diffs
+        .first()
+        .map(|diff| diff.unresolved.clone())
+        .unwrap_or_default();
+    unresolved.sort();
+
+    let unresolved_changed = !unresolved.is_empty() && unresolved != *reported_unresolved;
+    *reported_unresolved = unresolved;
+
+    if diffs.iter().all(WhitelistDiff::is_empty) && !unresolved_changed {
+        return Ok(());
+    }
+
+    if !unresolved_changed {
+        diffs.iter_mut().for_each(|diff| diff.unresolved.clear());
+    }
+
+    let embed = if apply {
+        let results =
+            apply_list_diffs(&data.rcon, &data.config.minecraft, &diffs, &whitelist).await;
+        build_whitelist_diff_embed(log_embed().title("Scheduled Whitelist Sync"), &results)
+    } else {
+        build_whitelist_diff_embed(log_embed().title("Whitelist Out Of Sync"), &diffs)
+            .description("Run `/whitelist sync` to apply these changes.")
+    };
+
+    send_log(ctx, data, embed).await
+}
+
+async fn send_log(
+    ctx: &serenity::Context,
+    data: &Data,
+    embed: serenity::CreateEmbed,
+) -> anyhow::Result<()> {
+    data.config
+        .channels
+        .bot_log
+        .send_message(ctx, CreateMessage::new().embed(embed))
+        .await?;
+
+    Ok(())
+}
@@ -5,25 +5,32 @@ mod config;
 mod database;
 mod error;
 mod events;
+mod jobs;
 mod util;
 
 use commands::{
-    animal, backup, help, info, mcskin, member, roletoggle, run, status, todo, whitelist,
+    animal, application, backup, help, info, mcskin, member, online, op, playtime, rolepanel,
+    roletoggle, run, seen, server, status, todo, whitelist,
 };
 use config::Config;
 use events::event_handler;
 
 use poise::serenity_prelude as serenity;
 use sqlx::postgres::PgPoolOptions;
+use util::{console::ConsoleManager, rcon::RconManager};
 
 #[derive(Debug, Clone)]
 pub struct Data {
     db_pool: sqlx::PgPool,
     config: Config,
+    rcon: RconManager,
+    console: ConsoleManager,
 }
 
 pub type Context<'a> = poise::Context<'a, Data, anyhow::Error>;
 
+const MESSAGE_CACHE_SIZE: usize = 200;
+
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing::subscriber::set_global_default(tracing_subscriber::fmt().compact().finish())?;
@@ -42,9 +49,13 @@ async fn main() -> anyhow::Result<()> {
         | serenity::GatewayIntents::GUILD_MEMBERS
         | serenity::GatewayIntents::GUILD_MODERATION
         | serenity::GatewayIntents::GUILD_MESSAGES
+        | serenity::GatewayIntents::MESSAGE_CONTENT
         | serenity::GatewayIntents::GUILD_MESSAGE_REACTIONS
         | serenity::GatewayIntents::GUILD_EMOJIS_AND_STICKERS;
 
+    let rcon = RconManager::new(&config.minecraft);
+    let console = ConsoleManager::new(&config.minecraft);
+
     let register_guild_id = config.bot.guild_id;
     let bot_token = config.bot.token.clone();
 
@@ -52,14 +63,21 @@ async fn main() -> anyhow::Result<()> {
         .options(poise::FrameworkOptions {
             commands: vec![
                 animal::animal(),
+                application::application(),
                 backup::backup(),
                 help::help(),
                 run::run(),
+                server::server(),
+                rolepanel::rolepanel(),
                 roletoggle::roletoggle(),
                 mcskin::mcskin(),
                 info::info(),
                 todo::todo(),
                 member::member(),
+                online::online(),
+                op::op(),
+                playtime::playtime(),
+                seen::seen(),
                 status::status(),
                 whitelist::whitelist(),
             ],
@@ -83,12 +101,27 @@ async fn main() -> anyhow::Result<()> {
                     register_guild_id,
                 )
                 .await?;
-                Ok(Data { db_pool, config })
+
+                let data = Data {
+                    db_pool,
+                    config,
+                    rcon,
+                    console,
+                };
+
+                jobs::spawn_jobs(ctx, &data);
+
+                Ok(data)
             })
         })
         .build();
 
+    // keeps recent messages around so edits and deletes can be logged with their old content
+    let mut cache_settings = serenity::cache::Settings::default();
+    cache_settings.max_messages = MESSAGE_CACHE_SIZE;
+
     let client = serenity::ClientBuilder::new(bot_token, client_intents)
+        .cache_settings(cache_settings)
         .framework(framework)
         .await;
 
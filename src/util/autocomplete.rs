@@ -0,0 +1,98 @@
+use poise::serenity_prelude as serenity;
+
+use crate::{
+    database::model::role_panel::RolePanelModelController,
+    util::{format::truncate, pterodactyl::PteroClient},
+    Context as AppContext,
+};
+
+pub async fn autocomplete_server(
+    ctx: AppContext<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice> {
+    let partial = partial.to_lowercase();
+
+    ctx.data()
+        .config
+        .minecraft
+        .iter()
+        .filter(|server| {
+            server.server_name.to_lowercase().starts_with(&partial)
+                || server.to_string().to_lowercase().starts_with(&partial)
+        })
+        .map(|server| {
+            serenity::AutocompleteChoice::new(server.to_string(), server.server_name.clone())
+        })
+        .collect()
+}
+
+/// Lists the backups of the server picked in the `server_choice` option, newest first.
+pub async fn autocomplete_backup(
+    ctx: AppContext<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice> {
+    let poise::Context::Application(app_ctx) = ctx else {
+        return Vec::new();
+    };
+
+    let Some(server_choice) = app_ctx.args.iter().find_map(|arg| match arg.value {
+        serenity::ResolvedValue::String(value) if arg.name == "server_choice" => Some(value),
+        _ => None,
+    }) else {
+        return Vec::new();
+    };
+
+    let Some(server_config) = ctx.data().config.minecraft.get(server_choice) else {
+        return Vec::new();
+    };
+
+    let mut backups = match PteroClient::backup_list(&ctx.data().config.pterodactyl, server_config)
+        .await
+    {
+        Ok(backups) => backups,
+        Err(e) => {
+            tracing::warn!("Failed to get the backups of {server_config} for autocompletion: {e}");
+            return Vec::new();
+        }
+    };
+
+    backups.sort_by_key(|b| std::cmp::Reverse(b.created_at));
+
+    let partial = partial.to_lowercase();
+
+    backups
+        .into_iter()
+        .filter(|backup| {
+            backup.uuid.to_string().starts_with(&partial)
+                || backup.name.to_lowercase().contains(&partial)
+        })
+        .take(25)
+        .map(|backup| {
+            let locked = if backup.is_locked { " [locked]" } else { "" };
+            let name = format!("{} ({}){locked}", backup.name, backup.created_at.date());
+
+            serenity::AutocompleteChoice::new(truncate(&name, 100), backup.uuid.to_string())
+        })
+        .collect()
+}
+
+pub async fn autocomplete_role_panel(
+    ctx: AppContext<'_>,
+    partial: &str,
+) -> Vec<serenity::AutocompleteChoice> {
+    let partial = partial.to_lowercase();
+
+    RolePanelModelController::get_all(&ctx.data().db_pool)
+        .await
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|panel| {
+            panel.id.to_string().starts_with(&partial)
+                || panel.title.to_lowercase().contains(&partial)
+        })
+        .take(25)
+        .map(|panel| {
+            serenity::AutocompleteChoice::new(format!("#{} {}", panel.id, panel.title), panel.id)
+        })
+        .collect()
+}
@@ -12,3 +12,9 @@ pub fn default_embed(user: &User) -> CreateEmbed {
         .footer(footer)
         .timestamp(chrono::Utc::now())
 }
+
+pub fn log_embed() -> CreateEmbed {
+    CreateEmbed::new()
+        .color(3_517_048)
+        .timestamp(chrono::Utc::now())
+}
@@ -0,0 +1,177 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use anyhow::Context;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::{client::IntoClientRequest, http::HeaderValue, Message};
+
+use crate::{
+    config::{MinecraftConfig, PterodactylConfig, ServerConfig},
+    util::pterodactyl::PteroClient,
+};
+
+const CONSOLE_BUFFER: usize = 1024;
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Fans out the console output of every server's pterodactyl websocket to any number of subscribers.
+#[derive(Debug, Clone)]
+pub struct ConsoleManager {
+    streams: Arc<HashMap<String, broadcast::Sender<String>>>,
+    connected: Arc<Mutex<HashSet<String>>>,
+}
+
+#[derive(Debug, Deserialize, Serialize)]
+struct SocketEvent {
+    event: String,
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+impl SocketEvent {
+    fn new(event: impl Into<String>, args: Vec<String>) -> Self {
+        Self {
+            event: event.into(),
+            args,
+        }
+    }
+
+    fn to_message(&self) -> anyhow::Result<Message> {
+        Ok(Message::Text(serde_json::to_string(self)?))
+    }
+}
+
+impl ConsoleManager {
+    pub fn new(minecraft_config: &MinecraftConfig) -> Self {
+        let streams = minecraft_config
+            .iter()
+            .map(|server| {
+                let (sender, _) = broadcast::channel(CONSOLE_BUFFER);
+                (server.server_name.clone(), sender)
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            streams: Arc::new(streams),
+            connected: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    pub fn subscribe(&self, server_config: &ServerConfig) -> Option<broadcast::Receiver<String>> {
+        self.streams
+            .get(&server_config.server_name)
+            .map(|sender| sender.subscribe())
+    }
+
+    /// Keeps the websocket connection to the panel alive for as long as the bot runs and publishes
+    /// every console line to the subscribers of this server. Connecting twice is a no-op.
+    pub fn connect(&self, ptero_config: &PterodactylConfig, server_config: &ServerConfig) {
+        let Some(sender) = self.streams.get(&server_config.server_name).cloned() else {
+            tracing::error!("There is no console stream registered for {server_config}");
+            return;
+        };
+
+        match self.connected.lock() {
+            Ok(mut connected) => {
+                if !connected.insert(server_config.server_name.clone()) {
+                    return;
+                }
+            }
+            Err(e) => {
+                tracing::error!("Failed to lock the console connections: {e}");
+                return;
+            }
+        }
+
+        let ptero_config = ptero_config.clone();
+        let server_config = server_config.clone();
+
+        tokio::spawn(async move {
+            let mut failed_attempts = 0;
+
+            loop {
+                match run_console_socket(&ptero_config, &server_config, &sender).await {
+                    Ok(_) => {
+                        tracing::info!("Console websocket for {server_config} was closed.");
+                        failed_attempts = 0;
+                    }
+                    Err(e) => {
+                        tracing::error!("Console websocket for {server_config} failed: {e:#}");
+                        failed_attempts += 1;
+                    }
+                }
+
+                let backoff =
+                    Duration::from_secs(2_u64.saturating_pow(failed_attempts)).min(MAX_BACKOFF);
+                tokio::time::sleep(backoff).await;
+            }
+        });
+    }
+}
+
+async fn run_console_socket(
+    ptero_config: &PterodactylConfig,
+    server_config: &ServerConfig,
+    sender: &broadcast::Sender<String>,
+) -> anyhow::Result<()> {
+    let credentials = PteroClient::websocket_credentials(ptero_config, server_config).await?;
+
+    let mut request = credentials.socket.as_str().into_client_request()?;
+    request
+        .headers_mut()
+        .insert("Origin", HeaderValue::from_str(&ptero_config.url)?);
+
+    let (mut socket, _) = tokio_tungstenite::connect_async(request)
+        .await
+        .context(format!(
+            "Failed to connect to the websocket of {server_config}"
+        ))?;
+
+    socket
+        .send(SocketEvent::new("auth", vec![credentials.token]).to_message()?)
+        .await?;
+
+    let ansi_escape = regex::Regex::new(r"\x1b\[[0-9;]*[A-Za-z]")?;
+
+    while let Some(message) = socket.next().await {
+        let text = match message? {
+            Message::Text(text) => text,
+            Message::Close(_) => break,
+            _ => continue,
+        };
+
+        let Ok(event) = serde_json::from_str::<SocketEvent>(&text) else {
+            continue;
+        };
+
+        match event.event.as_str() {
+            "auth success" => {
+                tracing::info!("Console websocket for {server_config} authenticated.");
+            }
+            "console output" => {
+                for line in event.args {
+                    // sending only fails if nobody is subscribed, which is fine
+                    let _ = sender.send(ansi_escape.replace_all(&line, "").into_owned());
+                }
+            }
+            "token expiring" => {
+                let credentials =
+                    PteroClient::websocket_credentials(ptero_config, server_config).await?;
+
+                socket
+                    .send(SocketEvent::new("auth", vec![credentials.token]).to_message()?)
+                    .await?;
+            }
+            "token expired" | "jwt error" => {
+                anyhow::bail!("Websocket token for {server_config} expired");
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
@@ -4,7 +4,7 @@ use serenity::all::{GuildChannel, PartialGuild, User};
 
 pub trait HasNameAndID {
     fn name(&self) -> &str;
-    fn id(&self) -> Cow<str>;
+    fn id(&self) -> Cow<'_, str>;
 }
 
 pub enum TimestampStyle {
@@ -22,7 +22,7 @@ impl HasNameAndID for User {
         &self.name
     }
 
-    fn id(&self) -> Cow<str> {
+    fn id(&self) -> Cow<'_, str> {
         self.id.to_string().into()
     }
 }
@@ -32,7 +32,7 @@ impl HasNameAndID for PartialGuild {
         &self.name
     }
 
-    fn id(&self) -> Cow<str> {
+    fn id(&self) -> Cow<'_, str> {
         self.id.to_string().into()
     }
 }
@@ -42,7 +42,7 @@ impl HasNameAndID for GuildChannel {
         &self.name
     }
 
-    fn id(&self) -> Cow<str> {
+    fn id(&self) -> Cow<'_, str> {
         self.id.to_string().into()
     }
 }
@@ -71,15 +71,29 @@ pub fn user_mention(user: &User) -> String {
     format!("<@{}>", user.id)
 }
 
+/// Shortens the input to at most `max` characters, marking it with "..." when it was cut off.
+pub fn truncate(input: &str, max: usize) -> String {
+    if input.chars().count() <= max {
+        return input.to_string();
+    }
+
+    let mut output = input
+        .chars()
+        .take(max.saturating_sub(3))
+        .collect::<String>();
+    output.push_str("...");
+    output
+}
+
 pub fn escape_markdown(input: impl Into<String>) -> String {
     let input = input.into();
     let mut output = String::with_capacity(input.len());
 
     for c in input.chars() {
-        if c.is_ascii_alphanumeric() {
-            output.push(c)
-        } else {
+        if c.is_ascii_punctuation() {
             output.extend(['\\', c])
+        } else {
+            output.push(c)
         }
     }
 
@@ -119,3 +133,16 @@ pub fn display_bytes(bytes: u64) -> String {
 
     format!("{:.2} {}", precise_count, sizes[i])
 }
+
+/// Formats a duration as days, hours and minutes, e.g. `2d 3h 15m`.
+pub fn display_duration(duration: chrono::Duration) -> String {
+    let days = duration.num_days();
+    let hours = duration.num_hours() % 24;
+    let minutes = duration.num_minutes() % 60;
+
+    match (days, hours) {
+        (0, 0) => format!("{minutes}m"),
+        (0, _) => format!("{hours}h {minutes}m"),
+        _ => format!("{days}d {hours}h {minutes}m"),
+    }
+}
@@ -0,0 +1,197 @@
+use std::{io::Cursor, sync::OnceLock};
+
+use chrono::{DateTime, Utc};
+use image::{ImageFormat, RgbImage};
+use plotters::prelude::*;
+
+use crate::{
+    commands::status::calculate_embed_color, database::model::server_metrics::MetricsSample,
+};
+
+const WIDTH: u32 = 1200;
+const HEIGHT: u32 = 800;
+const FONT: &str = "sans-serif";
+
+/// The MSPT values at which `calculate_embed_color` changes colour.
+const MSPT_THRESHOLDS: [i32; 3] = [30, 40, 50];
+
+static FONT_REGISTERED: OnceLock<bool> = OnceLock::new();
+
+/// Renders the MSPT, TPS and player count of the samples as a PNG. Samples further apart than
+/// `max_gap` are not connected, so downtime shows up as a gap instead of a straight line.
+pub fn render_metrics_graph(
+    title: &str,
+    samples: &[MetricsSample],
+    max_gap: Option<chrono::Duration>,
+) -> anyhow::Result<Vec<u8>> {
+    register_font()?;
+
+    let (Some(first), Some(last)) = (samples.first(), samples.last()) else {
+        anyhow::bail!("There are no samples to draw");
+    };
+
+    let start = first.recorded_at;
+    let end = last.recorded_at.max(start + chrono::Duration::minutes(1));
+
+    let max_mspt = samples.iter().map(|s| s.mspt).max().unwrap_or_default();
+    let mspt_range = 0..(max_mspt + max_mspt / 10).max(60);
+
+    let max_players = samples
+        .iter()
+        .map(|s| s.max_players.max(s.player_count))
+        .max()
+        .unwrap_or_default()
+        .max(1);
+
+    let mut buffer = vec![0; (WIDTH * HEIGHT * 3) as usize];
+
+    {
+        let root = BitMapBackend::with_buffer(&mut buffer, (WIDTH, HEIGHT)).into_drawing_area();
+        root.fill(&WHITE)?;
+
+        let root = root.titled(title, (FONT, 32))?;
+        let (upper, lower) = root.split_vertically(root.dim_in_pixel().1 * 3 / 5);
+
+        let mut performance = ChartBuilder::on(&upper)
+            .margin(10)
+            .x_label_area_size(30)
+            .y_label_area_size(50)
+            .right_y_label_area_size(50)
+            .build_cartesian_2d(start..end, mspt_range)?
+            .set_secondary_coord(start..end, 0..21);
+
+        performance
+            .configure_mesh()
+            .x_labels(8)
+            .x_label_formatter(&format_time)
+            .y_desc("MSPT")
+            .draw()?;
+
+        performance
+            .configure_secondary_axes()
+            .y_desc("TPS")
+            .draw()?;
+
+        for threshold in MSPT_THRESHOLDS {
+            let colour = to_rgb(calculate_embed_color(threshold as u32)).mix(0.4);
+
+            performance.draw_series(std::iter::once(PathElement::new(
+                vec![(start, threshold), (end, threshold)],
+                colour,
+            )))?;
+        }
+
+        // every segment is coloured by its worse end so lag spikes stand out
+        performance
+            .draw_series(segments(samples, max_gap).map(|[a, b]| {
+                let colour = to_rgb(calculate_embed_color(a.mspt.max(b.mspt).max(0) as u32));
+
+                PathElement::new(
+                    vec![(a.recorded_at, a.mspt), (b.recorded_at, b.mspt)],
+                    colour.stroke_width(2),
+                )
+            }))?
+            .label("MSPT")
+            .legend(|(x, y)| {
+                PathElement::new(vec![(x, y), (x + 20, y)], to_rgb(calculate_embed_color(0)))
+            });
+
+        performance.draw_series(samples.iter().filter(|s| s.mspt >= MSPT_THRESHOLDS[2]).map(
+            |s| {
+                Circle::new(
+                    (s.recorded_at, s.mspt),
+                    3,
+                    to_rgb(calculate_embed_color(s.mspt as u32)).filled(),
+                )
+            },
+        ))?;
+
+        performance
+            .draw_secondary_series(segments(samples, max_gap).map(|[a, b]| {
+                PathElement::new(
+                    vec![(a.recorded_at, a.tps), (b.recorded_at, b.tps)],
+                    BLUE.mix(0.6),
+                )
+            }))?
+            .label("TPS")
+            .legend(|(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], BLUE.mix(0.6)));
+
+        performance
+            .configure_series_labels()
+            .background_style(WHITE.mix(0.8))
+            .border_style(BLACK)
+            .draw()?;
+
+        let mut players = ChartBuilder::on(&lower)
+            .margin(10)
+            .x_label_area_size(40)
+            .y_label_area_size(50)
+            .right_y_label_area_size(50)
+            .build_cartesian_2d(start..end, 0..max_players + 1)?;
+
+        players
+            .configure_mesh()
+            .x_labels(8)
+            .x_label_formatter(&format_time)
+            .x_desc("Time (UTC)")
+            .y_desc("Players")
+            .draw()?;
+
+        players.draw_series(segments(samples, max_gap).map(|[a, b]| {
+            PathElement::new(
+                vec![
+                    (a.recorded_at, a.player_count),
+                    (b.recorded_at, b.player_count),
+                ],
+                MAGENTA.stroke_width(2),
+            )
+        }))?;
+
+        root.present()?;
+    }
+
+    let image = RgbImage::from_raw(WIDTH, HEIGHT, buffer)
+        .ok_or(anyhow::anyhow!("The graph buffer has the wrong size"))?;
+
+    let mut png = Cursor::new(Vec::new());
+    image.write_to(&mut png, ImageFormat::Png)?;
+
+    Ok(png.into_inner())
+}
+
+fn register_font() -> anyhow::Result<()> {
+    let registered = FONT_REGISTERED.get_or_init(|| {
+        plotters::style::register_font(
+            FONT,
+            FontStyle::Normal,
+            include_bytes!("../../assets/fonts/DejaVuSans.ttf"),
+        )
+        .is_ok()
+    });
+
+    if !registered {
+        anyhow::bail!("Failed to load the graph font");
+    }
+
+    Ok(())
+}
+
+fn segments(
+    samples: &[MetricsSample],
+    max_gap: Option<chrono::Duration>,
+) -> impl Iterator<Item = [&MetricsSample; 2]> {
+    samples
+        .windows(2)
+        .filter(move |pair| {
+            max_gap.is_none_or(|gap| pair[1].recorded_at - pair[0].recorded_at <= gap)
+        })
+        .map(|pair| [&pair[0], &pair[1]])
+}
+
+fn format_time(time: &DateTime<Utc>) -> String {
+    time.format("%d.%m. %H:%M").to_string()
+}
+
+fn to_rgb(colour: u32) -> RGBColor {
+    RGBColor((colour >> 16) as u8, (colour >> 8) as u8, colour as u8)
+}
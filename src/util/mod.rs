@@ -1,6 +1,12 @@
+pub mod autocomplete;
 pub mod builder;
+pub mod console;
 pub mod format;
+pub mod graph;
 pub mod mojang;
+pub mod net;
+pub mod paginate;
 pub mod pterodactyl;
 pub mod random_utils;
 pub mod rcon;
+pub mod server_list_ping;
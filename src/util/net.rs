@@ -0,0 +1,19 @@
+use std::net::SocketAddr;
+
+use anyhow::Context;
+
+/// Resolves a hostname, IPv4 or IPv6 literal (with or without brackets) to all of its socket addresses.
+pub async fn resolve_host(host: &str, port: u16) -> anyhow::Result<Vec<SocketAddr>> {
+    let host = host.trim().trim_start_matches('[').trim_end_matches(']');
+
+    let addrs = tokio::net::lookup_host((host, port))
+        .await
+        .context(format!("Failed to resolve host {host}"))?
+        .collect::<Vec<SocketAddr>>();
+
+    if addrs.is_empty() {
+        anyhow::bail!("Host {host} did not resolve to any address");
+    }
+
+    Ok(addrs)
+}
@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use poise::{serenity_prelude as serenity, CreateReply};
+use serenity::{
+    ButtonStyle, ComponentInteractionCollector, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage,
+};
+use tokio::time::Instant;
+
+use crate::{util::format::truncate, Context as AppContext};
+
+/// Discord allows 4096 characters in an embed description, the rest is headroom for the
+/// separators.
+const MAX_PAGE_LENGTH: usize = 4000;
+
+const PAGINATION_TIMEOUT: Duration = Duration::from_secs(10 * 60);
+
+/// The interaction token used to edit the reply expires after 15 minutes, so the buttons have to be
+/// removed well before that, no matter how often they were pressed.
+const SESSION_LIMIT: Duration = Duration::from_secs(13 * 60);
+
+/// Sends the items as the description of the embed, split into pages of at most `per_page` items
+/// that can be flipped through with buttons by the author of the command. The buttons are removed
+/// once nobody used them for ten minutes or the session limit is reached.
+pub async fn send_paginated(
+    ctx: AppContext<'_>,
+    embed: CreateEmbed,
+    items: Vec<String>,
+    per_page: usize,
+    separator: &str,
+) -> anyhow::Result<()> {
+    let pages = build_pages(items, per_page, separator);
+
+    if pages.len() <= 1 {
+        let description = pages.into_iter().next().unwrap_or_default();
+        ctx.send(CreateReply::default().embed(embed.description(description)))
+            .await?;
+        return Ok(());
+    }
+
+    let ctx_id = ctx.id();
+    let previous_id = format!("{ctx_id}_previous");
+    let next_id = format!("{ctx_id}_next");
+
+    let mut page = 0;
+
+    let handle = ctx
+        .send(
+            CreateReply::default()
+                .embed(embed.clone().description(pages[page].clone()))
+                .components(page_buttons(&previous_id, &next_id, page, pages.len())),
+        )
+        .await?;
+
+    let prefix = format!("{ctx_id}_");
+    let deadline = Instant::now() + SESSION_LIMIT;
+
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+
+        if remaining.is_zero() {
+            break;
+        }
+
+        let prefix = prefix.clone();
+
+        let Some(press) = ComponentInteractionCollector::new(ctx)
+            .author_id(ctx.author().id)
+            .filter(move |press| press.data.custom_id.starts_with(&prefix))
+            .timeout(remaining.min(PAGINATION_TIMEOUT))
+            .await
+        else {
+            break;
+        };
+
+        if press.data.custom_id == previous_id {
+            page = page.checked_sub(1).unwrap_or(pages.len() - 1);
+        } else if press.data.custom_id == next_id {
+            page = (page + 1) % pages.len();
+        } else {
+            continue;
+        }
+
+        let response = CreateInteractionResponse::UpdateMessage(
+            CreateInteractionResponseMessage::new()
+                .embed(embed.clone().description(pages[page].clone()))
+                .components(page_buttons(&previous_id, &next_id, page, pages.len())),
+        );
+
+        press.create_response(ctx, response).await?;
+    }
+
+    handle
+        .edit(
+            ctx,
+            CreateReply::default()
+                .embed(embed.description(pages[page].clone()))
+                .components(vec![]),
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Splits the items into pages of at most `per_page` items that fit into an embed description.
+fn build_pages(items: Vec<String>, per_page: usize, separator: &str) -> Vec<String> {
+    let mut pages = Vec::new();
+    let mut page = String::new();
+    let mut count = 0;
+
+    for item in items {
+        let item = truncate(&item, MAX_PAGE_LENGTH);
+
+        if count > 0
+            && (count >= per_page.max(1)
+                || page.len() + separator.len() + item.len() > MAX_PAGE_LENGTH)
+        {
+            pages.push(std::mem::take(&mut page));
+            count = 0;
+        }
+
+        if count > 0 {
+            page.push_str(separator);
+        }
+
+        page.push_str(&item);
+        count += 1;
+    }
+
+    if count > 0 {
+        pages.push(page);
+    }
+
+    pages
+}
+
+fn page_buttons(
+    previous_id: &str,
+    next_id: &str,
+    page: usize,
+    page_count: usize,
+) -> Vec<CreateActionRow> {
+    vec![CreateActionRow::Buttons(vec![
+        CreateButton::new(previous_id)
+            .label("Previous")
+            .style(ButtonStyle::Secondary),
+        CreateButton::new("page")
+            .label(format!("{}/{page_count}", page + 1))
+            .style(ButtonStyle::Secondary)
+            .disabled(true),
+        CreateButton::new(next_id)
+            .label("Next")
+            .style(ButtonStyle::Secondary),
+    ])]
+}
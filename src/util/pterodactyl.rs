@@ -3,16 +3,23 @@ use std::fmt::Display;
 use anyhow::Context;
 use pterodactyl_api::client::{
     backups::{Backup, BackupParams},
-    ClientBuilder, ServerState,
+    ClientBuilder, PowerSignal, ServerState,
 };
+use serde::Deserialize;
 use serenity::all::User;
 use uuid::Uuid;
 
-use crate::config::{PterodactylConfig, ServerChoice, ServerConfig};
+use crate::config::{PterodactylConfig, ServerConfig};
 
 pub struct PteroClient;
 pub struct DisplayServerState(pub ServerState);
 
+#[derive(Debug, Deserialize)]
+pub struct WebsocketCredentials {
+    pub token: String,
+    pub socket: String,
+}
+
 impl PteroClient {
     pub async fn server_state(
         ptero_config: &PterodactylConfig,
@@ -28,6 +35,52 @@ impl PteroClient {
         Ok(state)
     }
 
+    pub async fn send_power_signal(
+        ptero_config: &PterodactylConfig,
+        server_config: &ServerConfig,
+        signal: PowerSignal,
+    ) -> anyhow::Result<()> {
+        ClientBuilder::new(ptero_config.url.clone(), ptero_config.api_key.clone())
+            .build()
+            .get_server(server_config.panel_id.clone())
+            .send_power_signal(signal)
+            .await
+            .context(format!(
+                "Failed to send power signal {signal} to {server_config}"
+            ))
+    }
+
+    pub async fn websocket_credentials(
+        ptero_config: &PterodactylConfig,
+        server_config: &ServerConfig,
+    ) -> anyhow::Result<WebsocketCredentials> {
+        #[derive(Deserialize)]
+        struct WebsocketResponse {
+            data: WebsocketCredentials,
+        }
+
+        let url = format!(
+            "{}/api/client/servers/{}/websocket",
+            ptero_config.url.trim_end_matches('/'),
+            server_config.panel_id
+        );
+
+        let response = reqwest::Client::new()
+            .get(url)
+            .bearer_auth(&ptero_config.api_key)
+            .header("Accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()?
+            .json::<WebsocketResponse>()
+            .await
+            .context(format!(
+                "Failed to parse websocket credentials for {server_config}"
+            ))?;
+
+        Ok(response.data)
+    }
+
     pub async fn backup_list(
         ptero_config: &PterodactylConfig,
         server_config: &ServerConfig,
@@ -98,17 +151,65 @@ impl PteroClient {
         server_config: &ServerConfig,
         uuid: Uuid,
     ) -> anyhow::Result<()> {
-        let server_choice = ServerChoice::try_from(server_config)?;
-
         ClientBuilder::new(ptero_config.url.clone(), ptero_config.api_key.clone())
             .build()
             .get_server(server_config.panel_id.clone())
             .delete_backup(uuid)
             .await
             .context(format!(
-                "Failed to delete backup with id {uuid} from {server_choice}"
+                "Failed to delete backup with id {uuid} from {server_config}"
             ))
     }
+
+    /// The panel only offers toggling the lock of a backup, so the caller has to check the current
+    /// state first.
+    pub async fn toggle_backup_lock(
+        ptero_config: &PterodactylConfig,
+        server_config: &ServerConfig,
+        uuid: Uuid,
+    ) -> anyhow::Result<()> {
+        let url = format!(
+            "{}/api/client/servers/{}/backups/{uuid}/lock",
+            ptero_config.url.trim_end_matches('/'),
+            server_config.panel_id
+        );
+
+        reqwest::Client::new()
+            .post(url)
+            .bearer_auth(&ptero_config.api_key)
+            .header("Accept", "application/json")
+            .send()
+            .await?
+            .error_for_status()
+            .context(format!(
+                "Failed to toggle the lock of backup {uuid} on {server_config}"
+            ))?;
+
+        Ok(())
+    }
+
+    /// Reads the IGNs of all operators from the ops.json file, since rcon has no command to list them.
+    pub async fn operator_list(
+        ptero_config: &PterodactylConfig,
+        server_config: &ServerConfig,
+    ) -> anyhow::Result<Vec<String>> {
+        #[derive(Deserialize)]
+        struct Operator {
+            name: String,
+        }
+
+        let contents = ClientBuilder::new(ptero_config.url.clone(), ptero_config.api_key.clone())
+            .build()
+            .get_server(server_config.panel_id.clone())
+            .file_contents_text("ops.json")
+            .await
+            .context(format!("Failed to read ops.json of {server_config}"))?;
+
+        let operators = serde_json::from_str::<Vec<Operator>>(&contents)
+            .context(format!("Failed to parse ops.json of {server_config}"))?;
+
+        Ok(operators.into_iter().map(|o| o.name).collect())
+    }
 }
 
 impl Display for DisplayServerState {
@@ -1,6 +1,9 @@
 use std::borrow::BorrowMut;
 
-use serenity::all::{ButtonStyle, CreateActionRow, CreateButton, CreateEmbed, PartialGuild};
+use serenity::all::{
+    ButtonStyle, ComponentInteraction, Context, CreateActionRow, CreateButton, CreateEmbed,
+    CreateInteractionResponse, CreateInteractionResponseMessage, PartialGuild,
+};
 
 pub fn sort_player_list(player_list: &mut [String]) {
     player_list.sort_by(|a, b| {
@@ -43,3 +46,22 @@ pub fn confirm_cancel_component() -> Vec<CreateActionRow> {
 
     vec![action_row]
 }
+
+pub async fn respond_ephemeral(
+    component: &ComponentInteraction,
+    ctx: &Context,
+    content: impl Into<String>,
+) -> anyhow::Result<()> {
+    component
+        .create_response(
+            ctx,
+            CreateInteractionResponse::Message(
+                CreateInteractionResponseMessage::new()
+                    .content(content)
+                    .ephemeral(true),
+            ),
+        )
+        .await?;
+
+    Ok(())
+}
@@ -1,49 +1,210 @@
-use std::net::{Ipv4Addr, SocketAddr};
+use std::{collections::HashMap, fmt::Debug, sync::Arc, time::Duration};
 
-use rcon::Builder;
+use anyhow::Context;
+use rcon::{Builder, Connection};
+use tokio::{net::TcpStream, sync::Mutex, time::Instant};
 
-use crate::config::{Config, ServerChoice, ServerConfig};
+use crate::{
+    config::{MinecraftConfig, ServerConfig},
+    util::net::resolve_host,
+};
 
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(10);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+pub type RconResponse = anyhow::Result<Option<String>>;
+
+/// Keeps one authenticated rcon connection per configured server alive and hands it out to callers.
+#[derive(Clone)]
+pub struct RconManager {
+    slots: Arc<HashMap<String, Arc<Mutex<RconSlot>>>>,
+}
+
+#[derive(Default)]
+struct RconSlot {
+    connection: Option<Connection<TcpStream>>,
+    failed_attempts: u32,
+    retry_at: Option<Instant>,
+}
+
+impl RconManager {
+    pub fn new(minecraft_config: &MinecraftConfig) -> Self {
+        let slots = minecraft_config
+            .iter()
+            .map(|server| {
+                (
+                    server.server_name.clone(),
+                    Arc::new(Mutex::new(RconSlot::default())),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+
+        Self {
+            slots: Arc::new(slots),
+        }
+    }
+
+    fn slot(&self, config: &ServerConfig) -> anyhow::Result<Arc<Mutex<RconSlot>>> {
+        self.slots
+            .get(&config.server_name)
+            .cloned()
+            .context(format!(
+                "There is no rcon connection registered for {config}"
+            ))
+    }
+}
+
+impl Debug for RconManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RconManager")
+            .field("servers", &self.slots.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl RconSlot {
+    async fn connection(
+        &mut self,
+        config: &ServerConfig,
+    ) -> anyhow::Result<&mut Connection<TcpStream>> {
+        if self.connection.is_none() {
+            if let Some(retry_at) = self.retry_at {
+                let now = Instant::now();
+
+                if retry_at > now {
+                    anyhow::bail!(
+                        "Rcon on {config} is unavailable, retrying in {} seconds",
+                        (retry_at - now).as_secs().max(1)
+                    );
+                }
+            }
+
+            match connect(config).await {
+                Ok(connection) => {
+                    if self.failed_attempts > 0 {
+                        tracing::info!("Reconnected to rcon on {config}.");
+                    }
+
+                    self.connection = Some(connection);
+                    self.failed_attempts = 0;
+                    self.retry_at = None;
+                }
+                Err(e) => {
+                    self.failed_attempts += 1;
+
+                    let backoff = Duration::from_secs(2_u64.saturating_pow(self.failed_attempts))
+                        .min(MAX_BACKOFF);
+                    self.retry_at = Some(Instant::now() + backoff);
+
+                    return Err(e);
+                }
+            }
+        }
+
+        self.connection
+            .as_mut()
+            .context(format!("Rcon connection to {config} is missing"))
+    }
+}
+
+async fn connect(config: &ServerConfig) -> anyhow::Result<Connection<TcpStream>> {
+    let addrs = resolve_host(config.rcon_host(), config.rcon_port).await?;
+    let mut last_error = None;
+
+    for addr in addrs {
+        let connection = tokio::time::timeout(
+            CONNECT_TIMEOUT,
+            Builder::new()
+                .enable_minecraft_quirks(true)
+                .connect(addr, &config.rcon_password),
+        )
+        .await;
+
+        match connection {
+            Ok(Ok(connection)) => return Ok(connection),
+            Ok(Err(e)) => {
+                last_error = Some(
+                    anyhow::anyhow!(e)
+                        .context(format!("Failed to connect to rcon on {config} at {addr}")),
+                );
+            }
+            Err(_) => {
+                last_error = Some(anyhow::anyhow!(
+                    "Timed out connecting to rcon on {config} at {addr}"
+                ));
+            }
+        }
+    }
+
+    Err(last_error.unwrap_or(anyhow::anyhow!("Failed to connect to rcon on {config}")))
+}
+
+/// Runs the commands in order and returns one result per command. A failed command does not stop the
+/// remaining ones; the connection is dropped and re-established for the next command instead.
 pub async fn run_rcon_command(
+    rcon: &RconManager,
     config: &ServerConfig,
     commands: Vec<impl Into<String>>,
-) -> anyhow::Result<Vec<Option<String>>> {
-    let server = ServerChoice::try_from(config)?;
-
+) -> Vec<RconResponse> {
     let commands = commands
         .into_iter()
         .map(|c| c.into())
         .collect::<Vec<String>>();
 
-    let host = config.host.as_str().parse::<Ipv4Addr>()?;
-    let addr = SocketAddr::new(host.into(), config.rcon_port);
-
-    let mut connection = Builder::new()
-        .enable_minecraft_quirks(true)
-        .connect(addr, &config.rcon_password)
-        .await?;
+    let slot = match rcon.slot(config) {
+        Ok(slot) => slot,
+        Err(e) => {
+            return commands
+                .iter()
+                .map(|_| Err(anyhow::anyhow!("{e}")))
+                .collect();
+        }
+    };
 
-    let mut responses: Vec<Option<String>> = Vec::new();
+    let mut slot = slot.lock().await;
+    let mut responses: Vec<RconResponse> = Vec::with_capacity(commands.len());
 
     for cmd in commands {
-        let response = connection.cmd(cmd.as_str()).await;
+        let connection = match slot.connection(config).await {
+            Ok(connection) => connection,
+            Err(e) => {
+                tracing::error!("Error executing command \"{}\" on {config}: {e}", &cmd);
+                responses.push(Err(e));
+                continue;
+            }
+        };
+
+        let response = tokio::time::timeout(COMMAND_TIMEOUT, connection.cmd(cmd.as_str())).await;
 
         match response {
-            Ok(response) => {
-                tracing::info!("Command \"{}\" executed successfully on {server}.", &cmd,);
+            Ok(Ok(response)) => {
+                tracing::info!("Command \"{}\" executed successfully on {config}.", &cmd);
 
                 if response.is_empty() {
-                    responses.push(None);
+                    responses.push(Ok(None));
                 } else {
-                    responses.push(Some(response));
+                    responses.push(Ok(Some(response)));
                 }
             }
-            Err(e) => {
-                tracing::error!("Error executing command \"{}\" on {server}: {e}", &cmd,);
-                anyhow::bail!("Error executing command \"{}\" on {server}", &cmd);
+            Ok(Err(e)) => {
+                tracing::error!("Error executing command \"{}\" on {config}: {e}", &cmd);
+                slot.connection = None;
+                responses.push(Err(anyhow::anyhow!(
+                    "Error executing command \"{}\" on {config}",
+                    &cmd
+                )));
+            }
+            Err(_) => {
+                tracing::error!("Command \"{}\" timed out on {config}.", &cmd);
+                slot.connection = None;
+                responses.push(Err(anyhow::anyhow!(
+                    "Command \"{}\" timed out on {config}",
+                    &cmd
+                )));
             }
         }
     }
 
-    Ok(responses)
+    responses
 }
@@ -0,0 +1,222 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+use serde::Deserialize;
+use tokio::{
+    io::{AsyncRead, AsyncReadExt, AsyncWriteExt},
+    net::TcpStream,
+};
+
+use crate::util::net::resolve_host;
+
+const TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Status responses carry the server icon as base64, anything much larger is not a server.
+const MAX_PACKET_LENGTH: usize = 1024 * 1024;
+
+/// Servers answer status requests of any protocol version, -1 is what clients send when they
+/// don't know the version of the server yet.
+const PROTOCOL_VERSION: i32 = -1;
+
+const HANDSHAKE_PACKET_ID: i32 = 0x00;
+const STATUS_PACKET_ID: i32 = 0x00;
+const PING_PACKET_ID: i32 = 0x01;
+
+#[derive(Debug, Deserialize)]
+pub struct ServerStatus {
+    pub version: Option<StatusVersion>,
+    pub players: Option<StatusPlayers>,
+    #[serde(default)]
+    description: serde_json::Value,
+    #[serde(skip)]
+    pub latency: Duration,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatusVersion {
+    pub name: String,
+    pub protocol: i32,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct StatusPlayers {
+    pub online: i64,
+    pub max: i64,
+}
+
+impl ServerStatus {
+    /// The MOTD as plain text, without formatting codes.
+    pub fn motd(&self) -> String {
+        let mut motd = String::new();
+        flatten_text_component(&self.description, &mut motd);
+
+        strip_formatting_codes(&motd).trim().to_string()
+    }
+}
+
+/// Pings a Minecraft Java server using the Server List Ping protocol, the same handshake, status
+/// request and ping the client does for its server list.
+pub async fn ping_server(host: &str, port: u16) -> anyhow::Result<ServerStatus> {
+    tokio::time::timeout(TIMEOUT, ping(host, port))
+        .await
+        .context(format!(
+            "Timed out pinging {host}:{port} after {} seconds",
+            TIMEOUT.as_secs()
+        ))?
+}
+
+async fn ping(host: &str, port: u16) -> anyhow::Result<ServerStatus> {
+    let addrs = resolve_host(host, port).await?;
+
+    let mut stream = TcpStream::connect(addrs.as_slice())
+        .await
+        .context(format!("Failed to connect to {host}:{port}"))?;
+
+    let mut handshake = Vec::new();
+    write_var_int(&mut handshake, PROTOCOL_VERSION);
+    write_string(&mut handshake, host);
+    handshake.extend_from_slice(&port.to_be_bytes());
+    // the next state, 1 is status
+    write_var_int(&mut handshake, 1);
+
+    write_packet(&mut stream, HANDSHAKE_PACKET_ID, &handshake).await?;
+    write_packet(&mut stream, STATUS_PACKET_ID, &[]).await?;
+
+    let (id, response) = read_packet(&mut stream).await?;
+
+    if id != STATUS_PACKET_ID {
+        anyhow::bail!("Expected a status response but got packet {id:#04x}");
+    }
+
+    let mut response = response.as_slice();
+    let json = read_string(&mut response).await?;
+
+    let mut status = serde_json::from_str::<ServerStatus>(&json)
+        .context("Failed to parse the status response")?;
+
+    let payload = chrono::Utc::now().timestamp_millis();
+    let sent_at = Instant::now();
+
+    write_packet(&mut stream, PING_PACKET_ID, &payload.to_be_bytes()).await?;
+
+    let (id, pong) = read_packet(&mut stream).await?;
+    status.latency = sent_at.elapsed();
+
+    if id != PING_PACKET_ID || pong != payload.to_be_bytes() {
+        anyhow::bail!("The server answered the ping with an invalid pong");
+    }
+
+    Ok(status)
+}
+
+async fn write_packet(stream: &mut TcpStream, id: i32, data: &[u8]) -> anyhow::Result<()> {
+    let mut body = Vec::with_capacity(data.len() + 5);
+    write_var_int(&mut body, id);
+    body.extend_from_slice(data);
+
+    let mut packet = Vec::with_capacity(body.len() + 5);
+    write_var_int(&mut packet, i32::try_from(body.len())?);
+    packet.extend_from_slice(&body);
+
+    stream.write_all(&packet).await?;
+
+    Ok(())
+}
+
+async fn read_packet(stream: &mut TcpStream) -> anyhow::Result<(i32, Vec<u8>)> {
+    let length = usize::try_from(read_var_int(stream).await?)?;
+
+    if length > MAX_PACKET_LENGTH {
+        anyhow::bail!("The server sent a packet of {length} bytes");
+    }
+
+    let mut packet = vec![0; length];
+    stream.read_exact(&mut packet).await?;
+
+    let mut packet = packet.as_slice();
+    let id = read_var_int(&mut packet).await?;
+
+    Ok((id, packet.to_vec()))
+}
+
+fn write_var_int(buffer: &mut Vec<u8>, value: i32) {
+    let mut value = value as u32;
+
+    loop {
+        if value & !0x7F == 0 {
+            buffer.push(value as u8);
+            return;
+        }
+
+        buffer.push((value & 0x7F) as u8 | 0x80);
+        value >>= 7;
+    }
+}
+
+async fn read_var_int(reader: &mut (impl AsyncRead + Unpin)) -> anyhow::Result<i32> {
+    let mut value = 0u32;
+
+    for position in 0..5 {
+        let byte = reader.read_u8().await?;
+        value |= u32::from(byte & 0x7F) << (7 * position);
+
+        if byte & 0x80 == 0 {
+            return Ok(value as i32);
+        }
+    }
+
+    anyhow::bail!("VarInt is too long")
+}
+
+fn write_string(buffer: &mut Vec<u8>, value: &str) {
+    write_var_int(buffer, value.len() as i32);
+    buffer.extend_from_slice(value.as_bytes());
+}
+
+async fn read_string(reader: &mut (impl AsyncRead + Unpin)) -> anyhow::Result<String> {
+    let length = usize::try_from(read_var_int(reader).await?)?;
+
+    if length > MAX_PACKET_LENGTH {
+        anyhow::bail!("The server sent a string of {length} bytes");
+    }
+
+    let mut bytes = vec![0; length];
+    reader.read_exact(&mut bytes).await?;
+
+    Ok(String::from_utf8(bytes)?)
+}
+
+/// Descriptions are either plain strings or chat components with nested `extra` components.
+fn flatten_text_component(component: &serde_json::Value, output: &mut String) {
+    match component {
+        serde_json::Value::String(text) => output.push_str(text),
+        serde_json::Value::Array(components) => components
+            .iter()
+            .for_each(|component| flatten_text_component(component, output)),
+        serde_json::Value::Object(object) => {
+            if let Some(text) = object.get("text") {
+                flatten_text_component(text, output);
+            }
+
+            if let Some(extra) = object.get("extra") {
+                flatten_text_component(extra, output);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn strip_formatting_codes(input: &str) -> String {
+    let mut output = String::with_capacity(input.len());
+    let mut chars = input.chars();
+
+    while let Some(c) = chars.next() {
+        if c == '§' {
+            chars.next();
+        } else {
+            output.push(c);
+        }
+    }
+
+    output
+}